@@ -0,0 +1,256 @@
+//! Code generation for an enum whose variants all have named fields.
+//!
+//! Each variant gets its own builder as if it were a struct named
+//! `{Enum}{Variant}`, such as `CommandRunBuilder` for `Command::Run`,
+//! whose `build` returns the enum. `Command::builder()` returns
+//! `CommandBuilder`, which selects the variant to be built by the
+//! method named after it, such as `Command::builder().run()`.
+
+use super::*;
+
+/// The struct level attributes which are not able to be given on the
+/// enum, because the builders of the variants would collide with each
+/// other or a variant is not able to be converted into every builder.
+const ENUM_UNSUPPORTED_KEYS: &[&str] = &["name", "module", "build_with", "from_self", "template"];
+
+/// The struct level attributes which are not able to be given on a
+/// variant, because the variant has no `builder` function of its own
+/// or its fields are not able to be moved out of the enum.
+const VARIANT_UNSUPPORTED_KEYS: &[&str] = &["entry", "from_self", "template"];
+
+/// Check that the enum has at least one variant and every variant has
+/// named fields, so that `variant_input` is able to make a struct of it.
+pub(crate) fn validate_data(input: &DeriveInput, data: &syn::DataEnum) -> Result<(), syn::Error> {
+    if data.variants.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "Builder derive does not support enums without variants",
+        ));
+    }
+    let unnamed = data
+        .variants
+        .iter()
+        .find(|variant| !matches!(variant.fields, syn::Fields::Named(_)));
+    if let Some(variant) = unnamed {
+        return Err(syn::Error::new_spanned(
+            &variant.ident,
+            "Builder derive only supports enums whose variants all have named fields",
+        ));
+    }
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "Builder derive does not support generic enums",
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the error for the first key of `unsupported` in the
+/// `#[builder(...)]` attributes.
+fn validate_unsupported_keys(
+    attrs: &[syn::Attribute],
+    unsupported: &[&str],
+    place: &str,
+) -> Result<(), syn::Error> {
+    for meta in get_builder_meta_items(attrs) {
+        let path = match meta {
+            syn::NestedMeta::Meta(ref meta) => meta.path(),
+            syn::NestedMeta::Lit(_) => continue,
+        };
+        if let Some(key) = unsupported.iter().find(|key| is_path_eq(path, key)) {
+            return Err(syn::Error::new_spanned(
+                path,
+                format!("builder attribute `{}` is not supported on {}", key, place),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the input of the variant as if it were a struct named
+/// `{Enum}{Variant}` with the attributes of the enum followed by the
+/// `#[builder(...)]` attributes of the variant. It is marked by
+/// `#[builder_variant(Enum, Variant)]` for `origin_variant`.
+fn variant_input(input: &DeriveInput, variant: &syn::Variant) -> DeriveInput {
+    let enum_name = &input.ident;
+    let variant_name = &variant.ident;
+    let mut attrs = input.attrs.clone();
+    attrs.extend(
+        variant
+            .attrs
+            .iter()
+            .filter(|attr| is_path_eq(&attr.path, "builder"))
+            .cloned(),
+    );
+    attrs.push(syn::parse_quote!(#[builder_variant(#enum_name, #variant_name)]));
+    DeriveInput {
+        attrs,
+        vis: input.vis.clone(),
+        ident: format_ident!("{}{}", enum_name, variant_name, span = variant_name.span()),
+        generics: input.generics.clone(),
+        data: syn::Data::Struct(syn::DataStruct {
+            struct_token: Default::default(),
+            fields: variant.fields.clone(),
+            semi_token: None,
+        }),
+    }
+}
+
+/// Returns the enum and the variant which the builder builds, such as
+/// `(Command, Run)`, if the input is made by `variant_input`.
+pub(crate) fn origin_variant(input: &DeriveInput) -> Option<(syn::Ident, syn::Ident)> {
+    let attr = input
+        .attrs
+        .iter()
+        .find(|attr| is_path_eq(&attr.path, "builder_variant"))?;
+    let names = attr
+        .parse_args_with(
+            syn::punctuated::Punctuated::<syn::Ident, syn::Token![,]>::parse_terminated,
+        )
+        .ok()?;
+    let mut names = names.into_iter();
+    Some((names.next()?, names.next()?))
+}
+
+/// Returns the name of the method selecting the variant, which is the
+/// name of the variant in snake case such as `run_task` for `RunTask`.
+fn selector_fn_name(variant: &syn::Ident) -> syn::Ident {
+    let mut name = String::new();
+    for (i, c) in variant.unraw().to_string().chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                name.push('_');
+            }
+            name.extend(c.to_lowercase());
+        } else {
+            name.push(c);
+        }
+    }
+    if syn::parse_str::<syn::Ident>(&name).is_ok() {
+        format_ident!("{}", name, span = variant.span())
+    } else {
+        // a keyword such as `type` for `Type`.
+        format_ident!("r#{}", name, span = variant.span())
+    }
+}
+
+/// Generates the builders of the variants and the builder selecting
+/// one of them.
+pub(crate) fn derive(input: &DeriveInput, data: &syn::DataEnum) -> TokenStream {
+    let variants: Vec<(&syn::Variant, DeriveInput)> = data
+        .variants
+        .iter()
+        .map(|variant| (variant, variant_input(input, variant)))
+        .collect();
+
+    // The attributes of the enum are checked once here instead of for
+    // every variant.
+    let mut errors = Vec::new();
+    errors.extend(validate_unsupported_keys(&input.attrs, ENUM_UNSUPPORTED_KEYS, "an enum").err());
+    let (first, _) = variants[0];
+    let enum_only = DeriveInput {
+        attrs: input.attrs.clone(),
+        ..variant_input(input, first)
+    };
+    errors.extend(validate_container_attrs(&enum_only).err());
+    for (variant, _) in &variants {
+        errors.extend(
+            validate_unsupported_keys(&variant.attrs, VARIANT_UNSUPPORTED_KEYS, "a variant").err(),
+        );
+    }
+    if let Some(e) = combine_errors(errors) {
+        return e.to_compile_error();
+    }
+
+    let builders: TokenStream = variants
+        .iter()
+        .map(|(_, variant)| derive_struct(variant))
+        .collect();
+    let selector = ts_selector(input, &variants);
+
+    quote! {
+        #builders
+        #selector
+    }
+}
+
+/// This function returns `TokenStream` which represents
+/// a code such as
+/// ```ignore
+/// pub struct CommandBuilder;
+///
+/// impl CommandBuilder {
+///     pub fn run(self) -> CommandRunBuilder {
+///         CommandRunBuilder::new()
+///     }
+///
+///     pub fn stop(self) -> CommandStopBuilder {
+///         CommandStopBuilder::new()
+///     }
+/// }
+///
+/// impl Command {
+///     pub fn builder() -> CommandBuilder {
+///         CommandBuilder
+///     }
+/// }
+/// ```
+///
+/// where the method selecting the variant takes the fields of the
+/// variant with `#[builder(ctor_args)]` as `new` does, and `builder`
+/// is able to be renamed by `#[builder(entry = "...")]` on the enum.
+fn ts_selector(input: &DeriveInput, variants: &[(&syn::Variant, DeriveInput)]) -> TokenStream {
+    let enum_name = &input.ident;
+    let selector_name = format_ident!("{}Builder", enum_name);
+    let vis = builder_vis(input);
+    let entry = builder_attr_entry(input).unwrap_or_else(|_| format_ident!("builder"));
+    let must_use = ts_must_use_builder(input);
+
+    let selectors = variants.iter().map(|(variant, variant_input)| {
+        let fn_name = selector_fn_name(&variant.ident);
+        let builder_name = builder_export_name(variant_input);
+        let builder_ty_generics = initial_builder_ty_generics(variant_input);
+        let params = ctor_params(variant_input);
+        let args = ctor_args_fields(variant_input)
+            .into_iter()
+            .map(|field| field.ident.unwrap());
+        let doc = ts_doc(format!(
+            "Creates a builder of `{}::{}`.",
+            enum_name, variant.ident
+        ));
+        let must_use = ts_must_use_builder(variant_input);
+        quote! {
+            #doc
+            #must_use
+            #vis fn #fn_name(self, #(#params),*) -> #builder_name #builder_ty_generics {
+                #builder_name::new(#(#args),*)
+            }
+        }
+    });
+    let selector_doc = ts_doc(format!(
+        "The builder of `{}`, which selects the variant to be built.",
+        enum_name
+    ));
+    let entry_doc = ts_doc(format!("Creates a builder of `{}`.", enum_name));
+
+    quote! {
+        #selector_doc
+        #[derive(Clone, Copy, Debug, Default)]
+        #vis struct #selector_name;
+
+        #[automatically_derived]
+        impl #selector_name {
+            #(#selectors)*
+        }
+
+        #[automatically_derived]
+        impl #enum_name {
+            #entry_doc
+            #must_use
+            #vis fn #entry() -> #selector_name {
+                #selector_name
+            }
+        }
+    }
+}
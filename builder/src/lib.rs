@@ -1,25 +1,78 @@
 extern crate proc_macro;
 
+mod enums;
+mod typestate;
+
 use proc_macro::TokenStream as StdTokenStream;
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, quote_spanned};
+use syn::ext::IdentExt as _;
+use syn::parse::ParseStream;
+use syn::spanned::Spanned as _;
 use syn::{parse_macro_input, DeriveInput};
 
 #[proc_macro_derive(Builder, attributes(builder))]
 pub fn derive(tokens: StdTokenStream) -> StdTokenStream {
     let input = parse_macro_input!(tokens as DeriveInput);
 
+    if let Err(e) = validate_data(&input) {
+        return e.to_compile_error().into();
+    }
+    match input.data {
+        syn::Data::Enum(ref data) => enums::derive(&input, data),
+        _ => derive_struct(&input),
+    }
+    .into()
+}
+
+/// Generates the builder of the struct, or of a variant of an enum
+/// given as the struct by `enums::variant_input`.
+fn derive_struct(input: &DeriveInput) -> TokenStream {
+    if let Err(e) = validate_container_attrs(input) {
+        return e.to_compile_error();
+    }
+    // The errors of the field attributes are reported together with the
+    // builder, which ignores the wrong attributes, so that the uses of
+    // the builder do not report errors of their own.
+    let mut errors: Vec<syn::Error> = validate_fields(input).err().into_iter().collect();
+    if let Err(e) = validate_method_names(input) {
+        errors.push(e);
+        return combine_errors(errors).unwrap().to_compile_error();
+    }
+
+    let builder = vec![
+        ts_builder_struct(input),
+        ts_builder_impl_new_fn(input),
+        ts_builder_impl_field_consts(input),
+        ts_builder_impl_fields_fn(input),
+        typestate::ts_builder_impl_required_fields_fn(input),
+        ts_builder_impl_each_field_fn(input),
+        ts_builder_impl_extend(input),
+        ts_builder_impl_build_fn(input),
+        ts_builder_impl_try_build_fn(input),
+        typestate::ts_builder_impl_build_fn(input),
+        ts_builder_impl_build_or_panic_fn(input),
+        ts_builder_impl_build_wrappers_fn(input),
+        ts_builder_impl_missing_fields_fn(input),
+        ts_builder_impl_reset_fn(input),
+        ts_builder_impl_with_fn(input),
+        ts_builder_impl_mutators_fn(input),
+        ts_builder_impl_merge_fn(input),
+        ts_builder_impl_build_from_template_fn(input),
+        ts_builder_impl_debug(input),
+        ts_origin_impl_try_from(input),
+        ts_builder_impl_from_origin(input),
+    ]
+    .into_iter()
+    .collect::<TokenStream>();
+
     vec![
-        ts_origin_impl_builder_fn(&input),
-        ts_builder_struct(&input),
-        ts_builder_impl_new_fn(&input),
-        ts_builder_impl_fields_fn(&input),
-        ts_builder_impl_each_field_fn(&input),
-        ts_builder_impl_build_fn(&input),
+        ts_origin_impl_builder_fn(input),
+        ts_builder_module(input, builder),
+        combine_errors(errors).map_or_else(TokenStream::new, |e| e.to_compile_error()),
     ]
     .into_iter()
     .collect::<TokenStream>()
-    .into()
 }
 
 // ```
@@ -37,23 +90,129 @@ pub fn derive(tokens: StdTokenStream) -> StdTokenStream {
 // そのため、`String` を型名が期待される位置にinterpolate
 // すると上記のようなエラーが出る。
 fn origin_name(input: &DeriveInput) -> syn::Ident {
-    input.ident.clone()
+    match enums::origin_variant(input) {
+        Some((enum_name, _)) => enum_name,
+        None => input.ident.clone(),
+    }
 }
 
+/// Returns the name given by `#[builder(name = "...")]` or
+/// `{Struct}Builder` if the attribute is absent.
+/// Inside the module generated by `ts_builder_module`, the builder
+/// is named just `Builder` if the attribute is absent.
 fn builder_name(input: &DeriveInput) -> syn::Ident {
-    format_ident!("{}Builder", origin_name(input))
+    match builder_attr_name(input) {
+        Some(Ok(name)) => name,
+        _ if builder_attr_module(input).is_some() => format_ident!("Builder"),
+        _ => builder_export_name(input),
+    }
+}
+
+/// Returns the name of the builder which is visible next to the
+/// original struct, that is, `builder_name` or the name of the
+/// re-export of it generated by `ts_builder_module`.
+fn builder_export_name(input: &DeriveInput) -> syn::Ident {
+    match builder_attr_name(input) {
+        Some(Ok(name)) => name,
+        // `{Enum}{Variant}Builder` for a variant.
+        _ => format_ident!("{}Builder", input.ident),
+    }
+}
+
+/// Returns the name of the error type generated for the builder,
+/// such as `CommandBuilderError`, so that the error types of the
+/// structs in the same module do not collide with each other.
+/// Inside the module generated by `ts_builder_module`, it is named
+/// just `BuildError`.
+fn build_error_name(input: &DeriveInput) -> syn::Ident {
+    if builder_attr_module(input).is_some() {
+        format_ident!("BuildError")
+    } else {
+        format_ident!("{}Error", builder_export_name(input))
+    }
+}
+
+/// Check that the input is a struct with named fields, or an enum whose
+/// variants all have named fields, so that
+/// `origin_fields` is able to assume it.
+fn validate_data(input: &DeriveInput) -> Result<(), syn::Error> {
+    match input.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(_),
+            ..
+        }) => Ok(()),
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Unnamed(ref fields),
+            ..
+        }) => Err(syn::Error::new_spanned(
+            fields,
+            "Builder derive only supports structs with named fields",
+        )),
+        syn::Data::Struct(_) => Err(syn::Error::new_spanned(
+            &input.ident,
+            "Builder derive only supports structs with named fields",
+        )),
+        syn::Data::Enum(ref data) => enums::validate_data(input, data),
+        syn::Data::Union(_) => Err(syn::Error::new_spanned(
+            &input.ident,
+            "Builder derive only supports structs, not unions",
+        )),
+    }
+}
+
+/// Returns the fields which the builder has, that is, the fields of
+/// the original struct without `#[builder(skip)]`.
+fn builder_fields<'a>(input: &'a DeriveInput) -> impl Iterator<Item = syn::Field> + 'a {
+    origin_fields(input).filter(|field| !builder_attr_skip(field))
 }
 
 fn origin_fields<'a>(input: &'a DeriveInput) -> impl Iterator<Item = syn::Field> + 'a {
     let data = match input.data {
         syn::Data::Struct(ref data) => data,
-        _ => panic!("Builder derive only supports struct"),
+        // rejected by `validate_data`
+        _ => unreachable!(),
     };
 
-    match data.fields {
-        syn::Fields::Named(ref fields) => fields.named.iter().cloned(),
-        _ => panic!("Builder derive only supports named fields"),
-    }
+    let fields = match data.fields {
+        syn::Fields::Named(ref fields) => fields.named.iter(),
+        // rejected by `validate_data`
+        _ => unreachable!(),
+    };
+    let on_missing_default = builder_attr_on_missing_default(input);
+    fields.cloned().map(move |mut field| {
+        // `#[builder(on_missing = "default")]` is the same as
+        // `#[builder(default = "...")]` on every required field except
+        // the ones explicitly marked by `#[builder(required)]`.
+        if on_missing_default
+            && is_required_field(&field)
+            && !builder_attr_required(&field)
+            && !builder_attr_ctor_args(&field)
+        {
+            field.attrs.push(syn::parse_quote! {
+                #[builder(default = "::std::default::Default::default()")]
+            });
+        }
+        // `#[builder(flag)]` is `false` unless its setter is called.
+        if builder_attr_flag(&field)
+            && is_type_of(&field.ty, "bool")
+            && builder_attr_default(&field).is_none()
+        {
+            field.attrs.push(syn::parse_quote! {
+                #[builder(default = "false")]
+            });
+        }
+        field
+    })
+}
+
+/// Returns `#[cfg(...)]` attributes of the field, which are put on
+/// every item generated for the field.
+fn field_cfg_attrs(field: &syn::Field) -> Vec<&syn::Attribute> {
+    field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("cfg"))
+        .collect()
 }
 
 /// Returns `Type` of `T` in `Option<T>` or `Vec<T>` or something
@@ -61,24 +220,167 @@ fn origin_fields<'a>(input: &'a DeriveInput) -> impl Iterator<Item = syn::Field>
 /// Note that this function only be able to identify
 /// if the type is written literally as `Option<T>`,
 /// and not `std::option::Option<T>` or something like that.
+/// The type given by `#[builder(collection = "...")]` is used instead
+/// of the type of the field.
 fn single_generic_type_of(field: &syn::Field, type_name: &str) -> Option<syn::Type> {
-    // the `std` in `std::option::Option`.
-    let first_type_segment = match field.ty {
-        syn::Type::Path(ref path) => path.path.segments.first().unwrap(),
+    single_generic_type_in(&field_type(field), type_name)
+}
+
+/// Returns the type of the field, or the one given by
+/// `#[builder(collection = "...")]` which tells what a type alias
+/// such as `Args` stands for, since a proc-macro is not able to
+/// resolve it. The path without type arguments such as `im::Vector`
+/// takes the ones of the field, that is, `im::Vector<T>` for the
+/// field of `im::Vector<T>`.
+fn field_type(field: &syn::Field) -> syn::Type {
+    match builder_attr_collection(field) {
+        Some(Ok(syn::Type::Path(mut ty))) => {
+            let field_args = match field.ty {
+                syn::Type::Path(ref field_ty) => field_ty
+                    .path
+                    .segments
+                    .last()
+                    .map(|segment| segment.arguments.clone()),
+                _ => None,
+            };
+            if let (Some(last), Some(args)) = (ty.path.segments.last_mut(), field_args) {
+                if last.arguments.is_empty() {
+                    last.arguments = args;
+                }
+            }
+            syn::Type::Path(ty)
+        }
+        Some(Ok(ty)) => ty,
+        _ => field.ty.clone(),
+    }
+}
+
+/// Returns whether `#[builder(collection = "...")]` gives a collection
+/// other than `Vec`, such as `im::Vector<T>`, which starts with
+/// `im::Vector::new()` and whose items are added by `Extend` unless
+/// `#[builder(via = "...")]` is given.
+fn is_custom_collection(field: &syn::Field) -> bool {
+    if builder_attr_collection(field).is_none() {
+        return false;
+    }
+    let ty = field_type(field);
+    let ty = single_generic_type_in(&ty, "Option").unwrap_or(ty);
+    single_generic_type_in(&ty, "Vec").is_none()
+}
+
+/// Same as `single_generic_type_of` but takes `Type` instead of `Field`.
+/// Any other shape of type, such as `Option` without arguments or
+/// `Option<T>::Item`, is not considered to be `Option<T>`.
+fn single_generic_type_in(ty: &syn::Type, type_name: &str) -> Option<syn::Type> {
+    let path = match ty {
+        syn::Type::Path(ref path) if path.qself.is_none() => &path.path,
         _ => return None,
     };
-    if first_type_segment.ident == type_name {
-        let generic_arg = match first_type_segment.arguments {
-            syn::PathArguments::AngleBracketed(ref args) => args.args.first().unwrap(),
-            _ => unreachable!(),
-        };
-        match generic_arg {
-            syn::GenericArgument::Type(ref ty) => Some(ty.clone()),
-            _ => unreachable!(),
-        }
+    // `std::option::Option<T>` is not identified.
+    if path.leading_colon.is_some() || path.segments.len() != 1 {
+        return None;
+    }
+    let segment = path.segments.first()?;
+    if segment.ident != type_name {
+        return None;
+    }
+    let args = match segment.arguments {
+        syn::PathArguments::AngleBracketed(ref args) if args.args.len() == 1 => &args.args,
+        // a bare path such as a type parameter `T`, or `Fn(T) -> U`.
+        _ => return None,
+    };
+    match args.first()? {
+        syn::GenericArgument::Type(ref ty) => Some(ty.clone()),
+        _ => None,
+    }
+}
+
+/// Returns whether the field must be set before building,
+/// that is, the field is neither `Option<T>` nor `Vec<T>` and has
+/// none of `#[builder(default = ...)]`, `#[builder(default_try = ...)]`,
+/// `#[builder(keep_option)]` and `#[builder(skip)]`.
+/// A field of a bare type parameter `T` is also required, and so is
+/// a reference such as `&'a T` or `&'a Option<T>`, which the builder
+/// holds as `Option<&'a T>` and moves into the struct by `build`.
+/// `Option<T>` field with `#[builder(required)]` is required as well.
+/// Under `#[builder(on_missing = "default")]` the fields taken from
+/// `origin_fields` carry a default, so that only the latter is.
+fn is_required_field(field: &syn::Field) -> bool {
+    optional_type_of(field).is_none()
+        && !is_collection_field(field)
+        && builder_attr_default(field).is_none()
+        && builder_attr_default_try(field).is_none()
+        && !builder_attr_keep_option(field)
+        && !builder_attr_skip(field)
+}
+
+/// Returns whether the field is a collection which starts empty
+/// instead of being required, that is, `Vec<T>` or the field with
+/// `#[builder(via = "...")]`, `#[builder(map)]` or the collection
+/// given by `#[builder(collection = "...")]`.
+fn is_collection_field(field: &syn::Field) -> bool {
+    single_generic_type_of(field, "Vec").is_some()
+        || ((builder_attr_via(field).is_some()
+            || builder_attr_map(field)
+            || is_custom_collection(field))
+            && optional_type_of(field).is_none())
+}
+
+/// Returns the expression creating an empty collection of `ty`,
+/// which is `Vec::new()` or `<ty>::new()`.
+fn ts_new_collection(ty: &syn::Type) -> TokenStream {
+    if single_generic_type_in(ty, "Vec").is_some() {
+        quote! { ::std::vec::Vec::new() }
     } else {
+        quote! { <#ty>::new() }
+    }
+}
+
+/// Returns `T` of the `Option<T>` field, which is able to be left
+/// unset. `None` for `#[builder(required)]` or `#[builder(keep_option)]`
+/// field because its setter takes the whole `Option<T>`.
+fn optional_type_of(field: &syn::Field) -> Option<syn::Type> {
+    if builder_attr_required(field) || builder_attr_keep_option(field) {
         return None;
     }
+    single_generic_type_of(field, "Option")
+}
+
+/// Returns the type arguments of the last segment of the type,
+/// such as `K` and `V` of `indexmap::IndexMap<K, V>`.
+fn type_args(ty: &syn::Type) -> Vec<syn::Type> {
+    let segment = match ty {
+        syn::Type::Path(ref path) => match path.path.segments.last() {
+            Some(segment) => segment,
+            None => return Vec::new(),
+        },
+        _ => return Vec::new(),
+    };
+    match segment.arguments {
+        syn::PathArguments::AngleBracketed(ref args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                syn::GenericArgument::Type(ref ty) => Some(ty.clone()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns whether the type is written literally as `Cow<...>` or
+/// something like that, in the same way as `single_generic_type_of`.
+fn is_type_of(ty: &syn::Type, type_name: &str) -> bool {
+    match ty {
+        syn::Type::Path(ref path) => path
+            .path
+            .segments
+            .first()
+            .map(|segment| segment.ident == type_name)
+            .unwrap_or(false),
+        _ => false,
+    }
 }
 
 fn is_path_eq(path: &syn::Path, expected: &str) -> bool {
@@ -87,308 +389,5457 @@ fn is_path_eq(path: &syn::Path, expected: &str) -> bool {
 
 /// Look for `#[builder(...)]` attribues and get the value and
 /// return the `TokenStream` inside ().
-fn get_builder_meta_items<'a>(field: &'a syn::Field) -> impl Iterator<Item = syn::NestedMeta> + 'a {
-    field
-        .attrs
+fn get_builder_meta_items<'a>(
+    attrs: &'a [syn::Attribute],
+) -> impl Iterator<Item = syn::NestedMeta> + 'a {
+    get_builder_meta_lists(attrs).flat_map(|list| list.nested.into_iter())
+}
+
+/// Returns `builder(...)` of `#[builder(...)]` attributes.
+/// The attributes of the other shape are skipped here and reported
+/// by `validate_attr_format`.
+fn get_builder_meta_lists<'a>(
+    attrs: &'a [syn::Attribute],
+) -> impl Iterator<Item = syn::MetaList> + 'a {
+    attrs
         .iter()
         .filter(|attr| is_path_eq(&attr.path, "builder"))
-        .flat_map(|attr| match attr.parse_meta() {
-            Ok(syn::Meta::List(meta)) => meta.nested.into_iter(),
-            _ => panic!("Unsupported attribute format"),
+        .filter_map(|attr| match parse_builder_meta(attr) {
+            Ok(syn::Meta::List(list)) => Some(list),
+            _ => None,
         })
 }
 
+/// Parse `#[builder(...)]` attribute as `Meta`. Since `syn` only
+/// accepts a literal after `=`, the expression of `default = EXPR`
+/// is turned into the string literal of it, that is, the same as
+/// `default = "EXPR"`, and so is the path of `error = PATH`.
+fn parse_builder_meta(attr: &syn::Attribute) -> Result<syn::Meta, syn::Error> {
+    let meta = attr.parse_meta();
+    if meta.is_ok() {
+        return meta;
+    }
+    let paren_span = match attr.tokens.clone().into_iter().next() {
+        Some(proc_macro2::TokenTree::Group(group))
+            if group.delimiter() == proc_macro2::Delimiter::Parenthesis =>
+        {
+            group.span()
+        }
+        _ => return meta,
+    };
+    let nested = attr.parse_args_with(|input: ParseStream| {
+        syn::punctuated::Punctuated::parse_terminated_with(input, parse_nested_meta_or_unquoted)
+    });
+    match nested {
+        Ok(nested) => Ok(syn::Meta::List(syn::MetaList {
+            path: attr.path.clone(),
+            paren_token: syn::token::Paren(paren_span),
+            nested,
+        })),
+        // the error of `syn` is more helpful than the one of the expression.
+        Err(_) => meta,
+    }
+}
+
+/// Keys of `#[builder(...)]` attributes whose value is able to be
+/// written without quotes.
+const UNQUOTED_KEYS: &[&str] = &["default", "default_try", "error"];
+
+/// Parse `default = EXPR` as `default = "EXPR"`, so `default_try` as
+/// well, and `error = PATH` as `error = "PATH"`, or the other
+/// `NestedMeta` as it is.
+fn parse_nested_meta_or_unquoted(input: ParseStream) -> Result<syn::NestedMeta, syn::Error> {
+    let fork = input.fork();
+    let is_unquoted = matches!(
+        fork.parse::<syn::Ident>(),
+        Ok(ident) if UNQUOTED_KEYS.iter().any(|key| ident == key)
+    ) && fork.peek(syn::Token![=])
+        && !fork.peek2(syn::Lit);
+    if !is_unquoted {
+        return input.parse();
+    }
+    let path = syn::Path::from(input.parse::<syn::Ident>()?);
+    let eq_token = input.parse()?;
+    let expr: syn::Expr = input.parse()?;
+    let lit = syn::LitStr::new(&quote! { #expr }.to_string(), expr.span());
+    Ok(syn::NestedMeta::Meta(syn::Meta::NameValue(
+        syn::MetaNameValue {
+            path,
+            eq_token,
+            lit: syn::Lit::Str(lit),
+        },
+    )))
+}
+
+/// Check that every `builder` attribute is written as
+/// `#[builder(...)]`, not `#[builder]` nor `#[builder = "..."]`.
+fn validate_attr_format(attrs: &[syn::Attribute]) -> Result<(), syn::Error> {
+    let errors = attrs
+        .iter()
+        .filter(|attr| is_path_eq(&attr.path, "builder"))
+        .filter_map(|attr| match parse_builder_meta(attr) {
+            Ok(syn::Meta::List(_)) => None,
+            Ok(meta) => Some(syn::Error::new_spanned(
+                meta,
+                "expected `builder(key = \"value\")` or `builder(key)`",
+            )),
+            Err(e) => Some(e),
+        })
+        .collect();
+    combine_errors(errors).map_or(Ok(()), Err)
+}
+
 /// Look for `#[builder(each = "...")]` attribute and get the
 /// value of "...".
-fn builder_attr_each(field: &syn::Field) -> Option<Result<syn::LitStr, syn::Error>> {
-    get_builder_meta_items(field).find_map(|meta| match meta {
-        syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
-            ref path,
-            lit: syn::Lit::Str(ref s),
-            ..
-        })) => {
-            if is_path_eq(path, "each") {
-                Some(Ok(s.clone()))
-            } else {
-                Some(Err(syn::Error::new_spanned(
-                    meta,
-                    "expected `builder(each = \"...\")`",
-                )))
+fn builder_attr_each(field: &syn::Field) -> Option<syn::LitStr> {
+    find_builder_attr_str(&field.attrs, "each")
+}
+
+/// Parse `#[builder(each = "...")]` attribute as the name of the
+/// method adding an item.
+fn builder_attr_each_name(field: &syn::Field) -> Option<Result<syn::Ident, syn::Error>> {
+    builder_attr_each(field).map(|s| parse_ident_str(&s))
+}
+
+/// Look for `#[builder(collection = "...")]` attribute and parse the
+/// value of "..." as the type which the type of the field stands for.
+fn builder_attr_collection(field: &syn::Field) -> Option<Result<syn::Type, syn::Error>> {
+    let s = find_builder_attr_str(&field.attrs, "collection")?;
+    Some(
+        s.parse::<syn::Type>().map_err(|_| {
+            syn::Error::new_spanned(&s, format!("`{}` is not a valid type", s.value()))
+        }),
+    )
+}
+
+/// Look for `#[builder(validate = "...")]` attribute and parse the
+/// value of "..." as the path of the function checking the value of
+/// the field, such as `fn(&String) -> Result<(), String>`.
+fn builder_attr_validate(field: &syn::Field) -> Option<Result<syn::Path, syn::Error>> {
+    let s = find_builder_attr_str(&field.attrs, "validate")?;
+    Some(
+        s.parse::<syn::Path>().map_err(|_| {
+            syn::Error::new_spanned(&s, format!("`{}` is not a valid path", s.value()))
+        }),
+    )
+}
+
+/// Look for `#[builder(default = EXPR)]` or `#[builder(default = "EXPR")]`
+/// attribute and parse it as the expression of the value of the field
+/// used when its setter is not called, such as `DEFAULT_TIMEOUT`.
+fn builder_attr_default(field: &syn::Field) -> Option<Result<syn::Expr, syn::Error>> {
+    get_builder_meta_items(&field.attrs).find_map(|meta| match meta {
+        syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue { path, lit, .. }))
+            if is_path_eq(&path, "default") =>
+        {
+            match lit {
+                syn::Lit::Str(s) => Some(s.parse::<syn::Expr>().map_err(|_| {
+                    syn::Error::new_spanned(
+                        &s,
+                        format!("`{}` is not a valid expression", s.value()),
+                    )
+                })),
+                lit => Some(Ok(syn::Expr::Lit(syn::ExprLit {
+                    attrs: Vec::new(),
+                    lit,
+                }))),
             }
         }
         _ => None,
     })
 }
 
-/// This function returns `TokenStream` which represents
-/// a code such as
-/// ```ignore
-/// impl Command {
-///     fn builder() -> CommandBuilder {
-///         CommandBuilder::new()
-///     }
-/// }
-/// ```
-fn ts_origin_impl_builder_fn(input: &DeriveInput) -> TokenStream {
-    let origin_name = origin_name(input);
-    let builder_name = builder_name(input);
+/// Returns `true` if the expression of `#[builder(default = ...)]`
+/// refers to `self`, which is the builder, such as
+/// `self.executable.clone().unwrap_or_default()`. Such a default is
+/// evaluated by `ts_builder_defaults` before any value is taken out of
+/// the builder, so that it reads the values set to the other fields
+/// whichever order the fields are declared in.
+fn default_reads_builder(field: &syn::Field) -> bool {
+    fn has_self(tokens: TokenStream) -> bool {
+        tokens.into_iter().any(|token| match token {
+            proc_macro2::TokenTree::Ident(ident) => ident == "self",
+            proc_macro2::TokenTree::Group(group) => has_self(group.stream()),
+            _ => false,
+        })
+    }
+    match builder_attr_default(field) {
+        Some(Ok(default)) => has_self(quote! { #default }),
+        _ => false,
+    }
+}
 
-    quote! {
-        impl #origin_name {
-            fn builder() -> #builder_name {
-                #builder_name::new()
-            }
-        }
+/// Returns whether the default of the field is evaluated by
+/// `ts_builder_defaults` at the head of `build`, which is the one
+/// reading the builder and the one of the field with
+/// `#[builder(validate = "...")]`, which is checked before any value is
+/// taken out of the builder.
+fn has_early_default(field: &syn::Field) -> bool {
+    match builder_attr_default(field) {
+        Some(Ok(_)) => default_reads_builder(field) || builder_attr_validate(field).is_some(),
+        _ => false,
     }
 }
 
+/// Returns the name of the local variable holding the default of the
+/// field evaluated by `ts_builder_defaults`, such as `default_program`,
+/// or the one of `#[builder(default_try = ...)]`.
+fn default_local_name(field: &syn::Field) -> syn::Ident {
+    let name = field.ident.as_ref().unwrap().unraw();
+    format_ident!("default_{}", name, span = proc_macro2::Span::mixed_site())
+}
+
 /// This function returns `TokenStream` which represents
 /// a code such as
-///
 /// ```ignore
-/// struct CommandBuilder {
-///     executable: Option<String>,
-///     // optional field
-///     current_dir: Option<String>,
-/// }
+/// let default_program = if !self.__set[1] {
+///     Some(self.executable.clone().unwrap_or_default())
+/// } else {
+///     None
+/// };
 /// ```
 ///
-/// Note that original `Command` struct is such as
-///
-/// ```ignore
-/// struct Command {
-///     executable: String,
-///     // multiple value field
-///     args: Vec<String>,
-///     // optional field
-///     current_dir: Option<String>,
-/// }
-/// ```
-fn ts_builder_struct(input: &DeriveInput) -> TokenStream {
-    let builder_name = builder_name(input);
-    let builder_fields: TokenStream = origin_fields(input)
+/// for each field whose default reads the builder or is validated, which
+/// is put at the head of `build` so that the defaults see the builder as
+/// it is. See `has_early_default`. The
+/// default is only evaluated if the field is not set, and `self.executable`
+/// is the value set to the builder, not the default of `executable`.
+fn ts_builder_defaults(input: &DeriveInput) -> TokenStream {
+    origin_fields(input)
+        .filter(has_early_default)
         .map(|field| {
-            let name = field.ident.as_ref().unwrap();
-            if let Some(ty) = single_generic_type_of(&field, "Option") {
-                quote! {
-                    #name: std::option::Option<#ty>,
-                }
-            } else if let Some(ty) = single_generic_type_of(&field, "Vec") {
-                quote! {
-                    #name: std::vec::Vec<#ty>,
-                }
+            let local = default_local_name(&field);
+            let cfgs = field_cfg_attrs(&field);
+            let default = builder_attr_default(&field).unwrap().unwrap();
+            let value = if builder_attr_skip(&field) {
+                quote! { #default }
             } else {
-                let ty = field.ty;
+                let is_set = ts_is_set(input, &field, quote! { self });
+                let default = if optional_type_of(&field).is_some() {
+                    quote! { #default }
+                } else {
+                    quote! { ::std::option::Option::Some(#default) }
+                };
                 quote! {
-                    #name : std::option::Option<#ty>,
+                    if !#is_set {
+                        #default
+                    } else {
+                        ::std::option::Option::None
+                    }
                 }
+            };
+            quote! {
+                #(#cfgs)*
+                let #local = #value;
             }
         })
-        .collect();
+        .collect()
+}
+
+/// Look for `#[builder(default_try = EXPR)]` or
+/// `#[builder(default_try = "EXPR")]` attribute and parse it as the
+/// expression of `Result<T, E>` used when the setter is not called,
+/// such as `std::env::var("CARGO")`, whose `Err` fails `build`.
+fn builder_attr_default_try(field: &syn::Field) -> Option<Result<syn::Expr, syn::Error>> {
+    let s = find_builder_attr_str(&field.attrs, "default_try")?;
+    Some(s.parse::<syn::Expr>().map_err(|_| {
+        syn::Error::new_spanned(&s, format!("`{}` is not a valid expression", s.value()))
+    }))
+}
+
+/// Returns the expression of `Result<T, Box<dyn Error + Send + Sync>>`
+/// evaluating `#[builder(default_try = ...)]`, such as
+/// ```ignore
+/// std::env::var("CARGO").map_err(|error| -> Box<dyn Error + Send + Sync> {
+///     Into::into(error)
+/// })
+/// ```
+/// The error is boxed by `Into`, which every `Error + Send + Sync` and
+/// `String` implement.
+fn ts_default_try(default_try: &syn::Expr) -> TokenStream {
     quote! {
-        struct #builder_name {
-            #builder_fields
-        }
+        ::std::result::Result::map_err(
+            #default_try,
+            |error| -> ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync> {
+                ::std::convert::Into::into(error)
+            },
+        )
     }
 }
 
-///
-/// This function returns `TokenStream` which represents
-/// a code such as
+/// Returns the statement such as
 /// ```ignore
-/// impl CommandBuilder {
-///     pub fn new() -> CommandBuilder {
-///         CommandBuilder {
-///             executable: None,
-///             current_dir: None,
-///         }
+/// let default_program = if !self.__set[1] {
+///     match std::env::var("CARGO").map_err(...) {
+///         Ok(value) => Some(value),
+///         Err(source) => return Err(CommandBuilderError::InitError { field: "program", source }),
 ///     }
-/// }
+/// } else {
+///     None
+/// };
 /// ```
 ///
-fn ts_builder_impl_new_fn(input: &DeriveInput) -> TokenStream {
-    let builder_name = builder_name(input);
-    let builder_initial_fields: TokenStream = origin_fields(input)
-        .map(|field| {
-            let name = field.ident.as_ref().unwrap();
-            if single_generic_type_of(&field, "Vec").is_some() {
-                quote! {
-                    #name: std::vec::Vec::new(),
-                }
-            } else {
-                quote! {
-                    #name: std::option::Option::None,
-                }
+/// which evaluates `#[builder(default_try = ...)]` of the field not set
+/// before any value is taken out of the builder, so that its error
+/// leaves the builder as it is. The error is pushed to `errors` of
+/// `try_build` instead, which leaves the local `None`.
+fn ts_default_try_local(
+    input: &DeriveInput,
+    field: &syn::Field,
+    errors: Option<&syn::Ident>,
+) -> TokenStream {
+    let default_try = match builder_attr_default_try(field) {
+        Some(Ok(default_try)) => ts_default_try(&default_try),
+        _ => return TokenStream::new(),
+    };
+    let local = default_local_name(field);
+    let cfgs = field_cfg_attrs(field);
+    let is_set = ts_is_set(input, field, quote! { self });
+    let init = ts_init_field_error(input, field);
+    let on_error = match errors {
+        Some(errors) => quote! {
+            {
+                #errors.push(#init);
+                ::std::option::Option::None
             }
-        })
-        .collect();
-
+        },
+        None => quote! { return ::std::result::Result::Err(#init) },
+    };
     quote! {
-        impl #builder_name {
-            pub fn new() -> #builder_name {
-                #builder_name {
-                    #builder_initial_fields
-                }
+        #(#cfgs)*
+        let #local = if !#is_set {
+            match #default_try {
+                ::std::result::Result::Ok(value) => ::std::option::Option::Some(value),
+                ::std::result::Result::Err(source) => #on_error,
             }
-        }
+        } else {
+            ::std::option::Option::None
+        };
     }
 }
 
-/// This function returns `TokenStream` which represents
-/// a code such as
-/// ```ignore
-/// impl CommandBuilder {
-///     pub fn executable(&mut self, item: String) -> &mut Self {
-///         self.executable = Some(item);
-///         self
-///     }
+/// Returns the expression which fills the value of the field taken
+/// out of the builder by its default if the field is not set, such as
+/// `if self.__set[0] { value.unwrap() } else { DEFAULT_TIMEOUT }`, if
+/// `#[builder(default = ...)]` is given. The default of `Option<T>`
+/// field is `Option<T>` as well, and a collection is held as it is, so
+/// that the one set to be `None` or empty stays so.
 ///
-///     pub fn current_dir(&mut self, item: String) -> &mut Self {
-///         self.current_dir = Some(item);
-///         self
-///     }
-/// }
-/// ```
-fn ts_builder_impl_fields_fn(input: &DeriveInput) -> TokenStream {
-    let builder_name = builder_name(input);
-    let builder_fn_fields: TokenStream = origin_fields(input)
-        .filter(|field| {
-            // #[builder(each = "...")] の値と同じ場合はスキップする
-            match builder_attr_each(field) {
-                Some(Ok(ref s)) => *field.ident.as_ref().unwrap() != s.value(),
-                _ => true,
+/// `#[builder(keep_option)]` field is stored as `Option<Option<T>>`
+/// and its default is `None` unless it is given.
+///
+/// `#[builder(skip)]` field is not in the builder, so that `value` is
+/// ignored and the default, or `Default::default()`, is returned.
+///
+/// The default evaluated early is the local variable declared by
+/// `ts_builder_defaults` instead.
+fn ts_default_field(input: &DeriveInput, field: &syn::Field, value: TokenStream) -> TokenStream {
+    if builder_attr_skip(field) {
+        // the builder has no value of the field.
+        return match builder_attr_default(field) {
+            // evaluated by `ts_builder_defaults`.
+            Some(Ok(_)) if has_early_default(field) => {
+                let default = default_local_name(field);
+                quote! { #default }
             }
-        })
-        .map(|field| {
-            let name = field.ident.as_ref().unwrap();
-            // `T` when field type is `Option<T>` or `T`.
-            if single_generic_type_of(&field, "Vec").is_some() {
-                let ty = field.ty;
-                quote! {
-                    pub fn #name(&mut self, item: #ty) -> &mut Self {
-                        self.#name = item;
-                        self
-                    }
-                }
+            Some(Ok(default)) => quote! { #default },
+            _ => quote! { ::std::default::Default::default() },
+        };
+    }
+    let default = match builder_attr_default(field) {
+        // evaluated by `ts_builder_defaults`.
+        Some(Ok(_)) if has_early_default(field) => {
+            let default = default_local_name(field);
+            if optional_type_of(field).is_some() {
+                quote! { #default }
             } else {
-                let ty = single_generic_type_of(&field, "Option").unwrap_or(field.ty);
-                quote! {
-                    pub fn #name(&mut self, item: #ty) -> &mut Self {
-                        self.#name = Some(item);
-                        self
-                    }
-                }
+                quote! { #default.unwrap_or_else(|| ::std::unreachable!()) }
             }
-        })
-        .collect();
-
+        }
+        Some(Ok(default)) => quote! { #default },
+        None if builder_attr_keep_option(field) => quote! { ::std::option::Option::None },
+        // the wrong one is reported by `validate_fields`.
+        Some(Err(_)) if optional_type_of(field).is_none() && !is_collection_field(field) => {
+            quote! { ::std::unreachable!() }
+        }
+        _ => return value,
+    };
+    // the value is in `Option` unless the field is `Option<T>` or a collection.
+    let value = if optional_type_of(field).is_some() || is_collection_field(field) {
+        value
+    } else {
+        quote! { ::std::option::Option::unwrap_or_else(#value, || ::std::unreachable!()) }
+    };
+    let is_set = ts_is_set(input, field, quote! { self });
     quote! {
-        impl #builder_name {
-            #builder_fn_fields
+        if #is_set {
+            #value
+        } else {
+            #default
         }
     }
 }
 
-/// This function returns `TokenStream` which represents
-/// a code such as
-/// ```ignore
-/// impl CommandBuilder {
-///     pub fn args(&mut self, item: String) -> &mut Self {
-///         self.args.push(item);
-///         self
-///     }
-/// }
-/// ```
-fn ts_builder_impl_each_field_fn(input: &DeriveInput) -> TokenStream {
-    let builder_name = builder_name(input);
-    let builder_funcs: TokenStream = origin_fields(input)
-        .filter_map(|field| match builder_attr_each(&field) {
-            Some(Err(e)) => Some(e.to_compile_error()),
-            Some(Ok(each_fn_name_str)) => {
-                let each_fn_name = syn::Ident::new(
-                    each_fn_name_str.value().as_ref(),
-                    proc_macro2::Span::call_site(),
-                );
-                let name = field.ident.as_ref().unwrap();
-                let ty = single_generic_type_of(&field, "Vec").expect(
-                    "#[builder(each = \"...\")] attribute is only able to be set on `Vec` type",
-                );
+/// Look for `#[builder(map)]` attribute on the field.
+fn builder_attr_map(field: &syn::Field) -> bool {
+    find_builder_attr_path(&field.attrs, "map").is_some()
+}
 
-                let ts = quote! {
-                    pub fn #each_fn_name(&mut self, item: #ty) -> &mut Self {
-                        self.#name.push(item);
-                        self
-                    }
-                };
-                Some(ts)
-            }
+/// Look for `#[builder(merge = "append")]` attribute on the field,
+/// which makes `merge` and `build_from_template` append the items
+/// instead of replacing them.
+fn builder_attr_merge_append(field: &syn::Field) -> bool {
+    matches!(
+        find_builder_attr_str(&field.attrs, "merge"),
+        Some(s) if s.value() == "append"
+    )
+}
+
+/// What the setter of the field with `#[builder(once)]` does when the
+/// field has already been set.
+#[derive(Clone, Copy, PartialEq)]
+enum OnceMode {
+    /// `#[builder(once)]` or `#[builder(once = "panic")]`, which panics.
+    Panic,
+    /// `#[builder(once = "error")]`, which makes `build` return an error.
+    Error,
+}
+
+/// Look for `#[builder(once)]` or `#[builder(once = "...")]` attribute
+/// on the field, which rejects setting the field twice.
+fn builder_attr_once(field: &syn::Field) -> Option<Result<OnceMode, syn::Error>> {
+    if find_builder_attr_path(&field.attrs, "once").is_some() {
+        return Some(Ok(OnceMode::Panic));
+    }
+    let s = find_builder_attr_str(&field.attrs, "once")?;
+    match s.value().as_str() {
+        "panic" => Some(Ok(OnceMode::Panic)),
+        "error" => Some(Ok(OnceMode::Error)),
+        _ => Some(Err(syn::Error::new_spanned(
+            s,
+            "expected `once`, `once = \"panic\"` or `once = \"error\"`",
+        ))),
+    }
+}
+
+/// Returns whether some field has `#[builder(once = "error")]`, with
+/// which the builder records the first field set twice.
+fn has_once_error(input: &DeriveInput) -> bool {
+    origin_fields(input).any(|field| matches!(builder_attr_once(&field), Some(Ok(OnceMode::Error))))
+}
+
+/// Look for `#[builder(<key>)]` attribute and get `<key>`.
+fn find_builder_attr_path(attrs: &[syn::Attribute], key: &str) -> Option<syn::Path> {
+    get_builder_meta_items(attrs).find_map(|meta| match meta {
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if is_path_eq(&path, key) => Some(path),
+        _ => None,
+    })
+}
+
+/// Look for `#[builder(via = "...")]` attribute and parse the value
+/// of "..." as the name of the method adding an item to the collection,
+/// which is used instead of `push`.
+fn builder_attr_via(field: &syn::Field) -> Option<Result<syn::Ident, syn::Error>> {
+    find_builder_attr_str(&field.attrs, "via").map(|s| parse_ident_str(&s))
+}
+
+/// Look for `#[builder(rename = "...")]` or
+/// `#[builder(setter(name = "..."))]` attribute and parse the value
+/// of "..." as the name of the setter.
+fn builder_attr_rename(field: &syn::Field) -> Option<Result<syn::Ident, syn::Error>> {
+    let rename = find_builder_attr_str(&field.attrs, "rename");
+    let setter_name = find_meta_str(get_builder_setter_items(&field.attrs), "name");
+    match (rename, setter_name) {
+        (Some(_), Some(s)) => Some(Err(syn::Error::new_spanned(
+            s,
+            "`setter(name = \"...\")` is not able to be used with `rename`",
+        ))),
+        (Some(s), None) | (None, Some(s)) => Some(parse_ident_str(&s)),
+        (None, None) => None,
+    }
+}
+
+/// Parse the string literal as an identifier. The error points at
+/// the literal and tells why it is not legal, suggesting the raw
+/// identifier for a keyword.
+fn parse_ident_str(s: &syn::LitStr) -> Result<syn::Ident, syn::Error> {
+    let value = s.value();
+    if value.is_empty() {
+        return Err(syn::Error::new_spanned(s, "identifier must not be empty"));
+    }
+    if value.contains(char::is_whitespace) {
+        return Err(syn::Error::new_spanned(
+            s,
+            format!(
+                "`{}` is not a valid identifier because it contains whitespace",
+                value
+            ),
+        ));
+    }
+    // `syn` accepts the keywords of 2018 edition as identifiers.
+    let is_2018_keyword = ["async", "await", "dyn", "try"].contains(&value.as_str());
+    match s.parse::<syn::Ident>() {
+        Ok(ident) if !is_2018_keyword => return Ok(ident),
+        _ => {}
+    }
+    let message = match s.parse_with(syn::Ident::parse_any) {
+        Ok(_) if ["self", "Self", "super", "crate"].contains(&value.as_str()) => format!(
+            "`{}` is a keyword and is not able to be the name of a method, use another name",
+            value
+        ),
+        Ok(_) => format!(
+            "`{0}` is a keyword and is not able to be the name of a method, \
+             use another name or the raw identifier `r#{0}`",
+            value
+        ),
+        Err(_) => format!("`{}` is not a valid identifier", value),
+    };
+    Err(syn::Error::new_spanned(s, message))
+}
+
+/// Returns the value of `#[builder(rename = "...")]` or the name of
+/// the field if the attribute is absent.
+fn renamed_field_name(field: &syn::Field) -> syn::Ident {
+    match builder_attr_rename(field) {
+        Some(Ok(rename)) => rename,
+        _ => field.ident.clone().unwrap(),
+    }
+}
+
+/// Look for `#[builder(flag)]` attribute on the `bool` field, whose
+/// setter takes no argument and sets `true`.
+fn builder_attr_flag(field: &syn::Field) -> bool {
+    find_builder_attr_path(&field.attrs, "flag").is_some()
+}
+
+/// Returns the name of the setter taking `bool` of the field with
+/// `#[builder(flag)]`, which is `set_{rename or field name}`.
+fn flag_set_fn_name(field: &syn::Field) -> syn::Ident {
+    let name = renamed_field_name(field);
+    format_ident!("set_{}", name.unraw(), span = name.span())
+}
+
+/// Returns the name of the setter of the field, which is
+/// `{prefix}{rename or field name}{suffix}`.
+fn setter_name(input: &DeriveInput, field: &syn::Field) -> syn::Ident {
+    let name = renamed_field_name(field);
+    let prefix = find_builder_attr_str(&input.attrs, "prefix").map(|s| s.value());
+    let suffix = find_builder_attr_str(&input.attrs, "suffix").map(|s| s.value());
+    if prefix.is_none() && suffix.is_none() {
+        return name;
+    }
+    format_ident!(
+        "{}{}{}",
+        prefix.unwrap_or_default(),
+        name,
+        suffix.unwrap_or_default(),
+        span = name.span()
+    )
+}
+
+/// Returns the name of the method generated by
+/// `#[builder(each = "...")]`, which is `{each_prefix}{each}`.
+/// Like the other methods of the builder, the name has the span of
+/// what it comes from, the literal of `each` here, so that the IDE
+/// jumps there from the call of the method.
+fn each_fn_name(input: &DeriveInput, field: &syn::Field) -> Option<syn::Ident> {
+    let each = builder_attr_each_name(field)?.ok()?;
+    match find_builder_attr_str(&input.attrs, "each_prefix") {
+        Some(each_prefix) => Some(format_ident!(
+            "{}{}",
+            each_prefix.value(),
+            each,
+            span = each.span()
+        )),
+        None => Some(each),
+    }
+}
+
+/// Returns the name of the method extending the `Vec` by the items of
+/// an iterator, which is `{each_prefix}{rename or field name}_all`.
+/// It is generated together with the method of `#[builder(each = "...")]`.
+fn each_all_fn_name(input: &DeriveInput, field: &syn::Field) -> Option<syn::Ident> {
+    builder_attr_each_name(field)?.ok()?;
+    let name = renamed_field_name(field);
+    let each_prefix = find_builder_attr_str(&input.attrs, "each_prefix")
+        .map(|s| s.value())
+        .unwrap_or_default();
+    Some(format_ident!(
+        "{}{}_all",
+        each_prefix,
+        name,
+        span = name.span()
+    ))
+}
+
+/// The shape of the value of a key in `#[builder(...)]` attributes.
+#[derive(Clone, Copy)]
+enum AttrShape {
+    /// `key`
+    Flag,
+    /// `key` or `key = false`
+    FlagOrBool,
+    /// `key = "..."`
+    Str,
+    /// `key` or `key = "..."`
+    FlagOrStr,
+    /// `key = ...` with any literal
+    Lit,
+    /// `key(...)`
+    List,
+    /// `key = "..."` or `key(...)`
+    StrOrList,
+}
+
+impl AttrShape {
+    fn accepts(self, meta: &syn::Meta) -> bool {
+        match (self, meta) {
+            (AttrShape::Flag, syn::Meta::Path(_)) => true,
+            (AttrShape::FlagOrBool, syn::Meta::Path(_)) => true,
+            (AttrShape::FlagOrBool, syn::Meta::NameValue(nv)) => {
+                matches!(nv.lit, syn::Lit::Bool(_))
+            }
+            (AttrShape::Str, syn::Meta::NameValue(nv)) => matches!(nv.lit, syn::Lit::Str(_)),
+            (AttrShape::FlagOrStr, syn::Meta::Path(_)) => true,
+            (AttrShape::FlagOrStr, syn::Meta::NameValue(nv)) => {
+                matches!(nv.lit, syn::Lit::Str(_))
+            }
+            (AttrShape::Lit, syn::Meta::NameValue(_)) => true,
+            (AttrShape::List, syn::Meta::List(_)) => true,
+            (AttrShape::StrOrList, syn::Meta::NameValue(nv)) => {
+                matches!(nv.lit, syn::Lit::Str(_))
+            }
+            (AttrShape::StrOrList, syn::Meta::List(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the message telling how to write the key.
+    fn expected(self, key: &str) -> String {
+        match self {
+            AttrShape::Flag => format!("expected `{}`", key),
+            AttrShape::FlagOrBool => format!("expected `{0}` or `{0} = false`", key),
+            AttrShape::Str => format!("expected `{} = \"...\"`", key),
+            AttrShape::FlagOrStr => format!("expected `{0}` or `{0} = \"...\"`", key),
+            AttrShape::Lit => format!("expected `{} = ...`", key),
+            AttrShape::List => format!("expected `{}(...)`", key),
+            AttrShape::StrOrList => format!("expected `{0} = \"...\"` or `{0}(...)`", key),
+        }
+    }
+}
+
+/// Keys of the field level `#[builder(...)]` attributes.
+const FIELD_ATTR_KEYS: &[(&str, AttrShape)] = &[
+    ("each", AttrShape::Str),
+    ("into", AttrShape::FlagOrBool),
+    ("boxed", AttrShape::Flag),
+    ("rename", AttrShape::Str),
+    ("required", AttrShape::Flag),
+    ("setter", AttrShape::List),
+    ("via", AttrShape::Str),
+    ("collection", AttrShape::Str),
+    ("ctor_args", AttrShape::Flag),
+    ("flag", AttrShape::Flag),
+    ("map", AttrShape::Flag),
+    ("merge", AttrShape::Str),
+    ("validate", AttrShape::Str),
+    ("default", AttrShape::Lit),
+    ("default_try", AttrShape::Str),
+    ("keep_option", AttrShape::Flag),
+    ("skip", AttrShape::Flag),
+    ("sensitive", AttrShape::Flag),
+    ("sub_builder", AttrShape::Flag),
+    ("once", AttrShape::FlagOrStr),
+    ("keep", AttrShape::Flag),
+];
+
+/// Keys of the field level `#[builder(setter(...))]` attributes.
+const FIELD_SETTER_KEYS: &[(&str, AttrShape)] = &[
+    ("name", AttrShape::Str),
+    ("skip", AttrShape::Flag),
+    ("transform", AttrShape::Str),
+];
+
+/// Pairs of the field level `#[builder(...)]` attributes which are
+/// not able to be set on the same field.
+const FIELD_ATTR_CONFLICTS: &[(&str, &str)] = &[
+    ("boxed", "into"),
+    ("default", "required"),
+    ("default", "default_try"),
+    ("keep_option", "required"),
+    ("keep_option", "each"),
+    ("each", "default"),
+    ("skip", "each"),
+    ("skip", "setter"),
+    ("skip", "rename"),
+    ("skip", "required"),
+    ("skip", "keep_option"),
+    ("skip", "into"),
+    ("skip", "boxed"),
+    ("skip", "validate"),
+    ("skip", "sensitive"),
+    ("skip", "default_try"),
+    ("skip", "merge"),
+    ("sub_builder", "into"),
+    ("sub_builder", "boxed"),
+    ("sub_builder", "required"),
+    ("sub_builder", "ctor_args"),
+    ("flag", "into"),
+    ("flag", "boxed"),
+    ("flag", "ctor_args"),
+    ("flag", "skip"),
+    ("once", "each"),
+    ("once", "skip"),
+    ("once", "ctor_args"),
+    ("once", "sub_builder"),
+    ("keep", "skip"),
+];
+
+/// Keys of the field level `#[builder(...)]` attributes which only
+/// change the setter, so that they make no sense with
+/// `#[builder(setter(skip))]`.
+const SETTER_ONLY_KEYS: &[&str] = &["rename", "into", "boxed", "keep_option", "once"];
+
+/// Look for `#[builder(setter(transform = "..."))]` attribute and parse
+/// the value of "..." as the closure applied to the argument of the
+/// setter, such as `|s: String| s.trim().to_owned()`, whose only
+/// parameter must have its type, which the setter takes.
+fn builder_attr_setter_transform(
+    field: &syn::Field,
+) -> Option<Result<(syn::ExprClosure, syn::Type), syn::Error>> {
+    let s = find_meta_str(get_builder_setter_items(&field.attrs), "transform")?;
+    let closure = match s.parse::<syn::ExprClosure>() {
+        Ok(closure) => closure,
+        Err(_) => {
+            return Some(Err(syn::Error::new_spanned(
+                &s,
+                format!("`{}` is not a closure", s.value()),
+            )))
+        }
+    };
+    let ty = match closure.inputs.iter().collect::<Vec<_>>()[..] {
+        [syn::Pat::Type(ref pat)] => (*pat.ty).clone(),
+        _ => {
+            return Some(Err(syn::Error::new_spanned(
+                &s,
+                "the closure of `setter(transform = \"...\")` must take one parameter \
+                 with its type, such as `|s: String| ...`",
+            )))
+        }
+    };
+    Some(Ok((closure, ty)))
+}
+
+/// Look for `#[builder(into)]` or `#[builder(into = false)]` attribute
+/// and get whether the setter of the field should take `impl Into<T>`.
+fn builder_attr_into(field: &syn::Field) -> Option<bool> {
+    get_builder_meta_items(&field.attrs).find_map(|meta| match meta {
+        syn::NestedMeta::Meta(syn::Meta::Path(ref path)) if is_path_eq(path, "into") => Some(true),
+        syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+            ref path,
+            lit: syn::Lit::Bool(ref b),
+            ..
+        })) if is_path_eq(path, "into") => Some(b.value),
+        _ => None,
+    })
+}
+
+/// Look for `#[builder(boxed)]` attribute on the field.
+fn builder_attr_boxed(field: &syn::Field) -> bool {
+    get_builder_meta_items(&field.attrs).any(|meta| match meta {
+        syn::NestedMeta::Meta(syn::Meta::Path(ref path)) => is_path_eq(path, "boxed"),
+        _ => false,
+    })
+}
+
+/// Look for `#[builder(ctor_args)]` attribute on the field, which
+/// makes the field a parameter of `builder` and `new`.
+fn builder_attr_ctor_args(field: &syn::Field) -> bool {
+    find_builder_attr_path(&field.attrs, "ctor_args").is_some()
+}
+
+/// Returns the fields with `#[builder(ctor_args)]` in declaration order.
+fn ctor_args_fields(input: &DeriveInput) -> Vec<syn::Field> {
+    builder_fields(input)
+        .filter(builder_attr_ctor_args)
+        .collect()
+}
+
+/// Returns the parameters of `builder` and `new` such as
+/// `executable: String`, one for each field with `#[builder(ctor_args)]`.
+fn ctor_params(input: &DeriveInput) -> Vec<TokenStream> {
+    ctor_args_fields(input)
+        .iter()
+        .map(|field| {
+            let name = field.ident.as_ref().unwrap();
+            let ty = &field.ty;
+            quote! { #name: #ty }
+        })
+        .collect()
+}
+
+/// Look for `#[builder(required)]` attribute on the field.
+fn builder_attr_required(field: &syn::Field) -> bool {
+    get_builder_meta_items(&field.attrs).any(|meta| match meta {
+        syn::NestedMeta::Meta(syn::Meta::Path(ref path)) => is_path_eq(path, "required"),
+        _ => false,
+    })
+}
+
+/// Look for `#[builder(skip)]` attribute on the field, which is not
+/// in the builder and is always built from its default.
+fn builder_attr_skip(field: &syn::Field) -> bool {
+    find_builder_attr_path(&field.attrs, "skip").is_some()
+}
+
+/// Look for `#[builder(setter(skip))]` attribute on the field, which
+/// is in the builder but has no setter. It makes sense only with
+/// `#[builder(default = ...)]` or `#[builder(each = "...")]`.
+fn builder_attr_setter_skip(field: &syn::Field) -> bool {
+    get_builder_setter_items(&field.attrs).any(|meta| match meta {
+        syn::NestedMeta::Meta(syn::Meta::Path(ref path)) => is_path_eq(path, "skip"),
+        _ => false,
+    })
+}
+
+/// Look for `#[builder(keep_option)]` attribute on the field.
+fn builder_attr_keep_option(field: &syn::Field) -> bool {
+    find_builder_attr_path(&field.attrs, "keep_option").is_some()
+}
+
+/// Look for `#[builder(keep)]` attribute on the field, with which
+/// `build` clones the value of the field and leaves it in the builder
+/// as `#[builder(clone_build)]` does for every field.
+fn builder_attr_keep(field: &syn::Field) -> bool {
+    find_builder_attr_path(&field.attrs, "keep").is_some()
+}
+
+/// Returns the argument type of the setter of `#[builder(boxed)]`
+/// field, whose type is `ty` or `Option<ty>`.
+/// `impl Fn() + 'static` for `Box<dyn Fn()>` and `T` for `Box<T>`.
+fn boxed_setter_arg_type(ty: &syn::Type) -> Option<TokenStream> {
+    match single_generic_type_in(ty, "Box")? {
+        syn::Type::TraitObject(syn::TypeTraitObject { ref bounds, .. }) => {
+            let has_lifetime = bounds
+                .iter()
+                .any(|bound| matches!(bound, syn::TypeParamBound::Lifetime(_)));
+            if has_lifetime {
+                Some(quote! { impl #bounds })
+            } else {
+                Some(quote! { impl #bounds + 'static })
+            }
+        }
+        inner => Some(quote! { #inner }),
+    }
+}
+
+/// Check the field level `#[builder(...)]` attributes which are not
+/// able to be reported by the function generating the code.
+/// The errors of all fields are reported at once.
+fn validate_fields(input: &DeriveInput) -> Result<(), syn::Error> {
+    let mut errors = Vec::new();
+    for field in origin_fields(input) {
+        errors.extend(validate_attr_format(&field.attrs).err());
+        errors.extend(
+            validate_attr_keys(get_builder_meta_lists(&field.attrs), FIELD_ATTR_KEYS).err(),
+        );
+        errors.extend(
+            validate_attr_keys(get_builder_setter_lists(&field.attrs), FIELD_SETTER_KEYS).err(),
+        );
+        errors.extend(validate_attr_conflicts(&field.attrs, FIELD_ATTR_CONFLICTS).err());
+        errors.extend(builder_attr_rename(&field).and_then(Result::err));
+        errors.extend(builder_attr_each_name(&field).and_then(Result::err));
+        errors.extend(builder_attr_setter_transform(&field).and_then(Result::err));
+        if builder_attr_setter_transform(&field).is_some() {
+            let conflict = get_builder_meta_items(&field.attrs).find(|meta| match meta {
+                syn::NestedMeta::Meta(ref meta) => ["into", "boxed", "sub_builder"]
+                    .iter()
+                    .any(|key| is_path_eq(meta.path(), key)),
+                syn::NestedMeta::Lit(_) => false,
+            });
+            if let Some(meta) = conflict {
+                errors.push(syn::Error::new_spanned(
+                    meta,
+                    "this attribute is not able to be used with `setter(transform = \"...\")`, \
+                     which decides the argument of the setter",
+                ));
+            }
+        }
+        if builder_attr_required(&field) && single_generic_type_of(&field, "Option").is_none() {
+            errors.push(syn::Error::new_spanned(
+                &field.ty,
+                "#[builder(required)] attribute is only able to be set on `Option` type",
+            ));
+        }
+        if builder_attr_setter_skip(&field) {
+            let setter_only = get_builder_meta_items(&field.attrs).find_map(|meta| {
+                let path = match meta {
+                    syn::NestedMeta::Meta(ref meta) => meta.path().clone(),
+                    syn::NestedMeta::Lit(_) => return None,
+                };
+                let key = SETTER_ONLY_KEYS.iter().find(|key| is_path_eq(&path, key))?;
+                Some((meta, key))
+            });
+            if let Some((meta, key)) = setter_only {
+                errors.push(syn::Error::new_spanned(
+                    meta,
+                    format!(
+                        "builder attribute `{}` is not able to be used with `setter(skip)` \
+                         because the field has no setter",
+                        key
+                    ),
+                ));
+            }
+        }
+        if builder_attr_setter_skip(&field)
+            && is_required_field(&field)
+            && !builder_attr_ctor_args(&field)
+        {
+            errors.push(syn::Error::new_spanned(
+                &field.ty,
+                "#[builder(setter(skip))] attribute requires `default = ...` \
+                 because the field is never set",
+            ));
+        }
+        if let Some(path) = find_builder_attr_path(&field.attrs, "sub_builder") {
+            if !is_required_field(&field) || sub_builder_type(&field).is_none() {
+                errors.push(syn::Error::new_spanned(
+                    path,
+                    "#[builder(sub_builder)] attribute is only able to be set on a required field \
+                     of a struct type deriving `Builder`",
+                ));
+            }
+        }
+        if let Some(path) = find_builder_attr_path(&field.attrs, "flag") {
+            if !is_type_of(&field.ty, "bool") {
+                errors.push(syn::Error::new_spanned(
+                    path,
+                    "#[builder(flag)] attribute is only able to be set on `bool` type",
+                ));
+            }
+        }
+        if let Some(path) = find_builder_attr_path(&field.attrs, "ctor_args") {
+            if !is_required_field(&field) {
+                errors.push(syn::Error::new_spanned(
+                    path,
+                    "#[builder(ctor_args)] attribute is only able to be set on a required field, \
+                     which has no default",
+                ));
+            }
+        }
+        if let Some(path) = find_builder_attr_path(&field.attrs, "keep") {
+            if builder_pattern(input) == BuilderPattern::Owned {
+                errors.push(syn::Error::new_spanned(
+                    path,
+                    "#[builder(keep)] attribute is not able to be used with the owned pattern \
+                     because `build` consumes the builder",
+                ));
+            }
+        }
+        if builder_attr_keep_option(&field) && single_generic_type_of(&field, "Option").is_none() {
+            errors.push(syn::Error::new_spanned(
+                &field.ty,
+                "#[builder(keep_option)] attribute is only able to be set on `Option` type",
+            ));
+        }
+        if builder_attr_boxed(&field) {
+            let ty = optional_type_of(&field).unwrap_or_else(|| field.ty.clone());
+            if boxed_setter_arg_type(&ty).is_none() {
+                errors.push(syn::Error::new_spanned(
+                    &field.ty,
+                    "#[builder(boxed)] attribute is only able to be set on `Box` type",
+                ));
+            }
+        }
+        errors.extend(builder_attr_via(&field).and_then(Result::err));
+        errors.extend(builder_attr_collection(&field).and_then(Result::err));
+        errors.extend(builder_attr_validate(&field).and_then(Result::err));
+        errors.extend(builder_attr_once(&field).and_then(Result::err));
+        errors.extend(builder_attr_default(&field).and_then(Result::err));
+        errors.extend(builder_attr_default_try(&field).and_then(Result::err));
+        if let Some(s) = find_builder_attr_str(&field.attrs, "default_try") {
+            if single_generic_type_of(&field, "Option").is_some() || is_collection_field(&field) {
+                errors.push(syn::Error::new_spanned(
+                    s,
+                    "#[builder(default_try = ...)] attribute is not able to be set on \
+                     `Option` or a collection, which is able to be left unset",
+                ));
+            }
+        }
+        if let Some(s) = find_builder_attr_str(&field.attrs, "merge") {
+            if s.value() != "append" && s.value() != "replace" {
+                errors.push(syn::Error::new_spanned(
+                    s,
+                    "expected `merge = \"append\"` or `merge = \"replace\"`",
+                ));
+            } else if !is_collection_field(&field) && builder_attr_each(&field).is_none() {
+                errors.push(syn::Error::new_spanned(
+                    s,
+                    "#[builder(merge = \"...\")] attribute is only able to be set on \
+                     a collection, whose items are able to be appended",
+                ));
+            }
+        }
+        let each = builder_attr_each(&field);
+        let via = find_builder_attr_str(&field.attrs, "via");
+        let map = find_builder_attr_path(&field.attrs, "map");
+        if let (None, Some(ref via)) = (&each, &via) {
+            errors.push(syn::Error::new_spanned(
+                via,
+                "#[builder(via = \"...\")] attribute requires `each = \"...\"`",
+            ));
+        }
+        if let (None, Some(ref map)) = (&each, &map) {
+            errors.push(syn::Error::new_spanned(
+                map,
+                "#[builder(map)] attribute requires `each = \"...\"`",
+            ));
+        }
+        if each.is_some() && each_item_type(&field).is_none() {
+            let message = if map.is_some() {
+                "#[builder(map)] attribute is only able to be set on \
+                 a map type with the types of the keys and the values, such as `HashMap<K, V>`"
+            } else if via.is_some() || is_custom_collection(&field) {
+                "#[builder(via = \"...\")] or #[builder(collection = \"...\")] attribute \
+                 is only able to be set on a collection type with the type of the items, \
+                 such as `Bag<T>`"
+            } else {
+                "#[builder(each = \"...\")] attribute is only able to be set on `Vec` or `Option<Vec>` type"
+            };
+            errors.push(syn::Error::new_spanned(&field.ty, message));
+        }
+    }
+    combine_errors(errors).map_or(Ok(()), Err)
+}
+
+/// Combine the errors into one which reports all of them, or `None`
+/// if there is no error.
+fn combine_errors(errors: Vec<syn::Error>) -> Option<syn::Error> {
+    errors.into_iter().fold(None, |combined, e| match combined {
+        Some(mut combined) => {
+            combined.combine(e);
+            Some(combined)
+        }
+        None => Some(e),
+    })
+}
+
+/// Returns `T` of the `Vec<T>` or `Option<Vec<T>>` field and whether
+/// the `Vec` is in `Option`. With `#[builder(via = "...")]` or the
+/// collection given by `#[builder(collection = "...")]`, `T` is
+/// the first type argument of the collection such as `Bag<T>`, or the
+/// element of the array for the fixed-capacity collection which takes
+/// its backing array such as `SmallVec<[T; 4]>`.
+/// With `#[builder(map)]`, `T` is `(K, V)` of the map such as
+/// `HashMap<K, V>` or `BTreeMap<K, V>`.
+fn each_item_type(field: &syn::Field) -> Option<(syn::Type, bool)> {
+    if builder_attr_via(field).is_some() || builder_attr_map(field) || is_custom_collection(field) {
+        let (ty, is_optional) = match optional_type_of(field) {
+            Some(ty) => (ty, true),
+            None => (field_type(field), false),
+        };
+        let mut args = type_args(&ty).into_iter();
+        let item = if builder_attr_map(field) {
+            let (key, value) = (args.next()?, args.next()?);
+            syn::parse_quote!((#key, #value))
+        } else {
+            match args.next()? {
+                syn::Type::Array(array) => *array.elem,
+                item => item,
+            }
+        };
+        return Some((item, is_optional));
+    }
+    if let Some(ty) = single_generic_type_of(field, "Vec") {
+        return Some((ty, false));
+    }
+    let ty = single_generic_type_in(&optional_type_of(field)?, "Vec")?;
+    Some((ty, true))
+}
+
+/// Returns the methods which are generated for the field, that is, the
+/// setter, the `each` methods, the flag setter and the mutator, with the
+/// span to report a collision at and the hint to avoid it.
+fn field_method_names(
+    input: &DeriveInput,
+    field: &syn::Field,
+) -> Vec<(syn::Ident, proc_macro2::Span, &'static str)> {
+    let field_name = field.ident.as_ref().unwrap();
+    let setter_name = setter_name(input, field);
+    // point at the attribute which gives the name if any.
+    let setter_span = find_builder_attr_str(&field.attrs, "rename")
+        .or_else(|| find_meta_str(get_builder_setter_items(&field.attrs), "name"))
+        .map_or_else(|| field_name.span(), |s| s.span());
+    let has_setter = !builder_attr_setter_skip(field);
+    // the methods named after the setter are renamed with it.
+    let rename_hint = "rename the setter by `#[builder(rename = \"...\")]` \
+                       or `#[builder(setter(name = \"...\"))]`";
+    let each_hint = "give the method another name by `#[builder(each = \"...\")]`";
+    let mut methods = Vec::new();
+    match (each_fn_name(input, field), builder_attr_each(field)) {
+        (Some(each_fn_name), Some(each)) => {
+            if each_fn_name != setter_name && has_setter {
+                methods.push((setter_name, setter_span, rename_hint));
+            }
+            methods.push((each_fn_name, each.span(), each_hint));
+            if let Some(each_all_fn_name) = each_all_fn_name(input, field) {
+                methods.push((each_all_fn_name, each.span(), each_hint));
+            }
+        }
+        _ if has_setter => methods.push((setter_name, setter_span, rename_hint)),
+        _ => {}
+    }
+    if has_setter && builder_attr_flag(field) {
+        methods.push((flag_set_fn_name(field), setter_span, rename_hint));
+    }
+    if has_mutator(input, field) {
+        methods.push((mutator_fn_name(field), setter_span, rename_hint));
+    }
+    methods
+}
+
+/// Returns whether a method of some field is named `name`. `reset`,
+/// `with`, `missing_fields` and `is_complete` are not generated if so,
+/// so that a field such as `reset: bool` keeps its setter.
+fn is_field_method_name(input: &DeriveInput, name: &str) -> bool {
+    builder_fields(input).any(|field| {
+        field_method_names(input, &field)
+            .iter()
+            .any(|(method, _, _)| method == name)
+    })
+}
+
+/// Check that no two methods of the builder have the same name,
+/// such as the `each` methods of two fields or the `each` method of
+/// a field and the setter of another field. The error tells how to
+/// rename the method of the field, since a field such as `new: bool`
+/// collides with a method of the builder.
+fn validate_method_names(input: &DeriveInput) -> Result<(), syn::Error> {
+    let (build_fn_name, _) = builder_attr_build_fn(input)?;
+    let mut methods: Vec<(syn::Ident, String)> = vec![
+        (format_ident!("new"), "as the constructor".to_string()),
+        (build_fn_name, "as the build function".to_string()),
+    ];
+    if builder_attr_panic_helper(input) {
+        methods.push((
+            format_ident!("build_or_panic"),
+            "as the build function".to_string(),
+        ));
+    }
+    if !typestate::builder_attr_typestate(input) && !is_infallible_build(input) {
+        methods.push((
+            format_ident!("try_build"),
+            "as the build function".to_string(),
+        ));
+    }
+    if builder_attr_merge(input) {
+        methods.push((format_ident!("merge"), "to merge the builders".to_string()));
+    }
+    for (fn_name, _) in build_wrapper_fn_names(input) {
+        methods.push((fn_name, "as the build function".to_string()));
+    }
+    if builder_attr_template(input) {
+        methods.push((
+            format_ident!("build_from_template"),
+            "as the build function".to_string(),
+        ));
+    }
+
+    for field in builder_fields(input) {
+        let field_name = field.ident.as_ref().unwrap();
+        for (method, span, hint) in field_method_names(input, &field) {
+            if let Some((_, other)) = methods.iter().find(|(name, _)| *name == method) {
+                return Err(syn::Error::new(
+                    span,
+                    format!(
+                        "method `{}` for field `{}` is already generated {}; {}",
+                        method, field_name, other, hint
+                    ),
+                ));
+            }
+            methods.push((method, format!("for field `{}`", field_name)));
+        }
+    }
+    Ok(())
+}
+
+/// Look for `#[builder(setter(into))]` attribute on the struct.
+fn builder_attr_setter_into(input: &DeriveInput) -> bool {
+    get_builder_setter_items(&input.attrs).any(|meta| match meta {
+        syn::NestedMeta::Meta(syn::Meta::Path(ref path)) => is_path_eq(path, "into"),
+        _ => false,
+    })
+}
+
+/// Returns the items in `#[builder(setter(...))]` attributes.
+fn get_builder_setter_items<'a>(
+    attrs: &'a [syn::Attribute],
+) -> impl Iterator<Item = syn::NestedMeta> + 'a {
+    get_builder_setter_lists(attrs).flat_map(|list| list.nested.into_iter())
+}
+
+/// Returns `setter(...)` in `#[builder(setter(...))]` attributes.
+fn get_builder_setter_lists<'a>(
+    attrs: &'a [syn::Attribute],
+) -> impl Iterator<Item = syn::MetaList> + 'a {
+    get_builder_meta_items(attrs).filter_map(|meta| match meta {
+        syn::NestedMeta::Meta(syn::Meta::List(list)) if is_path_eq(&list.path, "setter") => {
+            Some(list)
+        }
+        _ => None,
+    })
+}
+
+/// Returns whether the setter of the field takes `impl Into<T>`.
+/// The field level `#[builder(into)]` takes precedence over the
+/// struct level `#[builder(setter(into))]`.
+/// `Cow<'a, T>` or `Option<Cow<'a, T>>` field takes
+/// `impl Into<Cow<'a, T>>` by default so that it is able to be set
+/// from both `&T` and `T::Owned`.
+fn is_setter_into(input: &DeriveInput, field: &syn::Field) -> bool {
+    builder_attr_into(field).unwrap_or_else(|| {
+        let ty = optional_type_of(field).unwrap_or_else(|| field.ty.clone());
+        builder_attr_setter_into(input) || is_type_of(&ty, "Cow")
+    })
+}
+
+/// Look for `#[builder(<key> = "...")]` attribute and get the
+/// value of "...".
+fn find_builder_attr_str(attrs: &[syn::Attribute], key: &str) -> Option<syn::LitStr> {
+    find_meta_str(get_builder_meta_items(attrs), key)
+}
+
+/// Look for `<key> = "..."` in `metas` and get the value of "...".
+fn find_meta_str(
+    mut metas: impl Iterator<Item = syn::NestedMeta>,
+    key: &str,
+) -> Option<syn::LitStr> {
+    metas.find_map(|meta| match meta {
+        syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+            ref path,
+            lit: syn::Lit::Str(ref s),
+            ..
+        })) if is_path_eq(path, key) => Some(s.clone()),
+        _ => None,
+    })
+}
+
+/// Look for `#[builder(name = "...")]` attribute on the struct
+/// and parse the value of "..." as the name of the builder.
+fn builder_attr_name(input: &DeriveInput) -> Option<Result<syn::Ident, syn::Error>> {
+    let s = find_builder_attr_str(&input.attrs, "name")?;
+    let name = match s.parse::<syn::Ident>() {
+        Ok(name) => name,
+        Err(_) => {
+            return Some(Err(syn::Error::new_spanned(
+                &s,
+                format!("`{}` is not a valid identifier", s.value()),
+            )))
+        }
+    };
+    if name == input.ident {
+        return Some(Err(syn::Error::new_spanned(
+            s,
+            "builder name must differ from the name of the struct",
+        )));
+    }
+    Some(Ok(name))
+}
+
+/// Look for `#[builder(vis = "...")]` attribute on the struct
+/// and parse the value of "..." as the visibility of the builder.
+fn builder_attr_vis(input: &DeriveInput) -> Option<Result<syn::Visibility, syn::Error>> {
+    let s = find_builder_attr_str(&input.attrs, "vis")?;
+    Some(s.parse::<syn::Visibility>().map_err(|_| {
+        syn::Error::new_spanned(&s, format!("`{}` is not a valid visibility", s.value()))
+    }))
+}
+
+/// Returns the visibility given by `#[builder(vis = "...")]` or the
+/// visibility of the struct if the attribute is absent.
+fn builder_vis(input: &DeriveInput) -> syn::Visibility {
+    match builder_attr_vis(input) {
+        Some(Ok(vis)) => vis,
+        _ => input.vis.clone(),
+    }
+}
+
+/// Returns the visibility of the builder struct and its methods.
+/// It is `pub` inside the module generated by `ts_builder_module`,
+/// where the re-export gets the visibility of the builder instead.
+fn builder_item_vis(input: &DeriveInput) -> syn::Visibility {
+    match builder_attr_module(input) {
+        Some(_) => syn::parse_quote!(pub),
+        None => builder_vis(input),
+    }
+}
+
+/// Look for `#[builder(entry = "...")]` attribute on the struct
+/// and parse the value of "..." as the name of the function which
+/// creates the builder. `builder` is the default.
+fn builder_attr_entry(input: &DeriveInput) -> Result<syn::Ident, syn::Error> {
+    match find_builder_attr_str(&input.attrs, "entry") {
+        Some(s) => s.parse::<syn::Ident>().map_err(|_| {
+            syn::Error::new_spanned(&s, format!("`{}` is not a valid identifier", s.value()))
+        }),
+        None => Ok(format_ident!("builder")),
+    }
+}
+
+/// Look for `#[builder(module = "...")]` attribute on the struct
+/// and parse the value of "..." as the name of the module.
+fn builder_attr_module(input: &DeriveInput) -> Option<Result<syn::Ident, syn::Error>> {
+    let s = find_builder_attr_str(&input.attrs, "module")?;
+    Some(s.parse::<syn::Ident>().map_err(|_| {
+        syn::Error::new_spanned(&s, format!("`{}` is not a valid identifier", s.value()))
+    }))
+}
+
+/// Look for `#[builder(error = PATH)]` or `#[builder(error = "PATH")]`
+/// attribute on the struct and parse the value as the path of the
+/// error type, which is able to be shared by several builders.
+fn builder_attr_error(input: &DeriveInput) -> Option<Result<syn::Path, syn::Error>> {
+    let s = find_builder_attr_str(&input.attrs, "error")?;
+    Some(
+        s.parse::<syn::Path>().map_err(|_| {
+            syn::Error::new_spanned(&s, format!("`{}` is not a valid path", s.value()))
+        }),
+    )
+}
+
+/// Look for `#[builder(build_with = "...")]` attribute on the struct
+/// and parse the value of "..." as the path of the function which
+/// constructs the struct.
+fn builder_attr_build_with(input: &DeriveInput) -> Option<Result<syn::Path, syn::Error>> {
+    let s = find_builder_attr_str(&input.attrs, "build_with")?;
+    Some(
+        s.parse::<syn::Path>().map_err(|_| {
+            syn::Error::new_spanned(&s, format!("`{}` is not a valid path", s.value()))
+        }),
+    )
+}
+
+/// Look for `#[builder(wrap = "...")]` attribute on the struct and get
+/// the path of the smart pointer wrapping the built struct, which is
+/// one of `Arc`, `Rc` and `Box`.
+fn builder_attr_wrap(input: &DeriveInput) -> Option<Result<TokenStream, syn::Error>> {
+    let s = find_builder_attr_str(&input.attrs, "wrap")?;
+    Some(match s.value().as_str() {
+        "Arc" => Ok(quote! { ::std::sync::Arc }),
+        "Rc" => Ok(quote! { ::std::rc::Rc }),
+        "Box" => Ok(quote! { ::std::boxed::Box }),
+        _ => Err(syn::Error::new_spanned(
+            s,
+            "expected `wrap = \"Arc\"`, `wrap = \"Rc\"` or `wrap = \"Box\"`",
+        )),
+    })
+}
+
+/// Which receiver the setters and `build` method of the builder take.
+#[derive(Clone, Copy, PartialEq)]
+enum BuilderPattern {
+    /// `fn executable(&mut self, ..) -> &mut Self` and `fn build(&mut self)`.
+    Mutable,
+    /// `fn executable(mut self, ..) -> Self` and `fn build(self)`.
+    Owned,
+}
+
+/// Look for `#[builder(derive(...))]` attributes on the struct and get
+/// the paths of the derive macros to be put on the builder struct,
+/// such as `Clone` and `Debug`.
+fn builder_attr_derive(input: &DeriveInput) -> Result<Vec<syn::Path>, syn::Error> {
+    let mut derives = Vec::new();
+    for meta in get_builder_meta_items(&input.attrs) {
+        let list = match meta {
+            syn::NestedMeta::Meta(syn::Meta::List(list)) if is_path_eq(&list.path, "derive") => {
+                list
+            }
+            _ => continue,
+        };
+        for nested in list.nested {
+            match nested {
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) => derives.push(path),
+                nested => {
+                    return Err(syn::Error::new_spanned(
+                        nested,
+                        "expected the path of a derive macro such as `Clone`",
+                    ))
+                }
+            }
+        }
+    }
+    Ok(derives)
+}
+
+/// Look for `#[builder(debug)]` attribute on the struct.
+fn builder_attr_debug(input: &DeriveInput) -> bool {
+    find_builder_attr_path(&input.attrs, "debug").is_some()
+}
+
+/// Look for `#[builder(sub_builder)]` attribute on the field, whose
+/// value is built by the builder of its type.
+fn builder_attr_sub_builder(field: &syn::Field) -> bool {
+    find_builder_attr_path(&field.attrs, "sub_builder").is_some()
+}
+
+/// Returns the builder of the type of the field with
+/// `#[builder(sub_builder)]`, named as `derive(Builder)` does, such as
+/// `config::ListenerBuilder<T>` for `config::Listener<T>`. `None` if
+/// the type is not a path.
+fn sub_builder_type(field: &syn::Field) -> Option<syn::Type> {
+    let mut ty = match field.ty {
+        syn::Type::Path(ref ty) if ty.qself.is_none() => ty.clone(),
+        _ => return None,
+    };
+    let last = ty.path.segments.last_mut()?;
+    last.ident = format_ident!("{}Builder", last.ident, span = last.ident.span());
+    Some(syn::Type::Path(ty))
+}
+
+/// Returns the expression which builds the value of the field with
+/// `#[builder(sub_builder)]` out of its builder taken as `value`, such as
+/// ```ignore
+/// Option::map(value, |mut builder| {
+///     builder.build().map_err(|error| -> Box<dyn Error + Send + Sync> {
+///         Into::into(error)
+///     })
+/// })
+/// ```
+/// which is `None` if the builder is not set.
+fn ts_build_sub_builder(value: TokenStream) -> TokenStream {
+    let builder = syn::Ident::new("builder", proc_macro2::Span::mixed_site());
+    quote! {
+        ::std::option::Option::map(#value, |mut #builder| {
+            ::std::result::Result::map_err(
+                #builder.build(),
+                |error| -> ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync> {
+                    ::std::convert::Into::into(error)
+                },
+            )
+        })
+    }
+}
+
+/// Returns whether `build` builds the sub-builder of the field with
+/// `#[builder(sub_builder)]` in place instead of taking it out, which is
+/// with the mutable pattern unless `#[builder(clone_build)]` or
+/// `#[builder(keep)]` clones it. The sub-builder is left as it is if its
+/// `build` fails, which must take `&mut self` or `&self` then.
+fn builds_sub_builder_in_place(input: &DeriveInput, field: &syn::Field) -> bool {
+    builder_pattern(input) == BuilderPattern::Mutable
+        && !builder_attr_clone_build(input)
+        && !builder_attr_keep(field)
+}
+
+/// Returns the expression which builds the value of the field with
+/// `#[builder(sub_builder)]` in `build`, which is the one of
+/// `ts_build_sub_builder` or
+/// ```ignore
+/// Option::map(Option::as_mut(&mut self.listener), |builder| {
+///     builder.build().map_err(...)
+/// })
+/// ```
+/// if it is built in place. See `builds_sub_builder_in_place`.
+fn ts_build_sub_builder_field(input: &DeriveInput, field: &syn::Field) -> TokenStream {
+    if !builds_sub_builder_in_place(input, field) {
+        return ts_build_sub_builder(ts_take_field(input, field));
+    }
+    let name = field.ident.as_ref().unwrap();
+    let builder = syn::Ident::new("builder", proc_macro2::Span::mixed_site());
+    quote! {
+        ::std::option::Option::map(::std::option::Option::as_mut(&mut self.#name), |#builder| {
+            ::std::result::Result::map_err(
+                #builder.build(),
+                |error| -> ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync> {
+                    ::std::convert::Into::into(error)
+                },
+            )
+        })
+    }
+}
+
+/// Returns the name of the local variable holding the value built by
+/// the sub-builder of the field, such as `built_listener`.
+fn built_local_name(field: &syn::Field) -> syn::Ident {
+    let name = field.ident.as_ref().unwrap().unraw();
+    format_ident!("built_{}", name, span = proc_macro2::Span::mixed_site())
+}
+
+/// Returns the statement which puts the value built in place by the
+/// sub-builder of the field back into the builder, such as
+/// `self.listener = Some(From::from(built_listener));`, when a later
+/// step of `build` fails. The sub-builder is made by `From` of its own
+/// derive as `From` of the builder does.
+fn ts_restore_sub_builder(input: &DeriveInput, field: &syn::Field) -> TokenStream {
+    if !builds_sub_builder_in_place(input, field) {
+        return TokenStream::new();
+    }
+    let name = field.ident.as_ref().unwrap();
+    let built = built_local_name(field);
+    let cfgs = field_cfg_attrs(field);
+    quote! {
+        #(#cfgs)*
+        {
+            self.#name = ::std::option::Option::Some(::std::convert::From::from(#built));
+        }
+    }
+}
+
+/// Look for `#[builder(sensitive)]` attribute on the field, whose
+/// value is not printed by the `Debug` of `#[builder(debug)]`.
+fn builder_attr_sensitive(field: &syn::Field) -> bool {
+    find_builder_attr_path(&field.attrs, "sensitive").is_some()
+}
+
+/// Check that `#[builder(debug)]` is not given together with `Debug`
+/// in `#[builder(derive(...))]`, and that `#[builder(sensitive)]` is
+/// only given with `#[builder(debug)]`, since the derived `Debug` would
+/// print the value anyway.
+fn validate_builder_debug(input: &DeriveInput) -> Result<(), syn::Error> {
+    let derived_debug = builder_attr_derive(input)
+        .unwrap_or_default()
+        .into_iter()
+        .find(|path| matches!(path.segments.last(), Some(s) if s.ident == "Debug"));
+    match (builder_attr_debug(input), derived_debug) {
+        (true, Some(path)) => Err(syn::Error::new_spanned(
+            path,
+            "`Debug` is not able to be derived with `builder(debug)`, \
+             which implements `Debug` for the builder",
+        )),
+        (true, None) => Ok(()),
+        (false, _) => {
+            let errors = origin_fields(input).filter_map(|field| {
+                let path = find_builder_attr_path(&field.attrs, "sensitive")?;
+                Some(syn::Error::new_spanned(
+                    path,
+                    "#[builder(sensitive)] attribute requires `#[builder(debug)]` on the struct",
+                ))
+            });
+            combine_errors(errors.collect()).map_or(Ok(()), Err)
+        }
+    }
+}
+
+/// Look for `#[builder(setter(owned))]` attribute on the struct,
+/// which is the same as `#[builder(pattern = "owned")]`.
+fn builder_attr_setter_owned(input: &DeriveInput) -> Option<syn::Path> {
+    get_builder_setter_items(&input.attrs).find_map(|meta| match meta {
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if is_path_eq(&path, "owned") => Some(path),
+        _ => None,
+    })
+}
+
+/// Look for `#[builder(pattern = "...")]` or `#[builder(setter(owned))]`
+/// attribute on the struct. `BuilderPattern::Mutable` is the default.
+fn builder_attr_pattern(input: &DeriveInput) -> Result<BuilderPattern, syn::Error> {
+    let setter_owned = builder_attr_setter_owned(input);
+    match find_builder_attr_str(&input.attrs, "pattern") {
+        None if setter_owned.is_some() => Ok(BuilderPattern::Owned),
+        None => Ok(BuilderPattern::Mutable),
+        Some(s) => match s.value().as_str() {
+            "mutable" => match setter_owned {
+                Some(path) => Err(syn::Error::new_spanned(
+                    path,
+                    "`setter(owned)` is not able to be used with `builder(pattern = \"mutable\")`",
+                )),
+                None => Ok(BuilderPattern::Mutable),
+            },
+            "owned" => Ok(BuilderPattern::Owned),
+            _ => Err(syn::Error::new_spanned(
+                s,
+                "expected `builder(pattern = \"mutable\")` or `builder(pattern = \"owned\")`",
+            )),
+        },
+    }
+}
+
+/// Returns the pattern of the builder, which is always
+/// `BuilderPattern::Owned` with `#[builder(typestate)]`.
+fn builder_pattern(input: &DeriveInput) -> BuilderPattern {
+    if typestate::builder_attr_typestate(input) {
+        return BuilderPattern::Owned;
+    }
+    builder_attr_pattern(input).unwrap_or(BuilderPattern::Mutable)
+}
+
+/// Returns the generics of the builder, which are the ones of the
+/// struct followed by the const parameters of `#[builder(typestate)]`.
+fn builder_generics(input: &DeriveInput) -> syn::Generics {
+    let mut generics = input.generics.clone();
+    generics.params.extend(typestate::state_params(input));
+    generics
+}
+
+/// Returns the type arguments of the builder returned by `new`,
+/// such as `<'a, T>`, or `<'a, T, false>` with `#[builder(typestate)]`.
+fn initial_builder_ty_generics(input: &DeriveInput) -> TokenStream {
+    let args: Vec<TokenStream> = origin_generic_args(input)
+        .into_iter()
+        .chain(typestate::initial_state_args(input))
+        .collect();
+    if args.is_empty() {
+        quote! {}
+    } else {
+        quote! { <#(#args),*> }
+    }
+}
+
+/// Returns the generic arguments of the struct, such as `'a` and `T`
+/// for `struct Command<'a, T: Clone>`.
+fn origin_generic_args(input: &DeriveInput) -> Vec<TokenStream> {
+    input
+        .generics
+        .params
+        .iter()
+        .map(|param| match param {
+            syn::GenericParam::Lifetime(ref def) => {
+                let lifetime = &def.lifetime;
+                quote! { #lifetime }
+            }
+            syn::GenericParam::Type(ref ty) => {
+                let ident = &ty.ident;
+                quote! { #ident }
+            }
+            syn::GenericParam::Const(ref c) => {
+                let ident = &c.ident;
+                quote! { #ident }
+            }
+        })
+        .collect()
+}
+
+/// Returns the receiver and return type of setters,
+/// `&mut self` and `&mut Self` or `mut self` and `Self`.
+fn setter_self(input: &DeriveInput) -> (TokenStream, TokenStream) {
+    match builder_pattern(input) {
+        BuilderPattern::Mutable => (quote! { &mut self }, quote! { &mut Self }),
+        BuilderPattern::Owned => (quote! { mut self }, quote! { Self }),
+    }
+}
+
+/// Returns the receiver of the build function,
+/// `&mut self` or `self`, or `&self` with `#[builder(clone_build)]`.
+fn build_fn_self(input: &DeriveInput) -> TokenStream {
+    if builder_attr_clone_build(input) {
+        return quote! { &self };
+    }
+    match builder_pattern(input) {
+        BuilderPattern::Mutable => quote! { &mut self },
+        BuilderPattern::Owned => quote! { self },
+    }
+}
+
+/// Look for `#[builder(no_must_use)]` attribute on the struct.
+fn builder_attr_no_must_use(input: &DeriveInput) -> bool {
+    find_builder_attr_path(&input.attrs, "no_must_use").is_some()
+}
+
+/// Look for `#[builder(clone_build)]` attribute on the struct, with which
+/// `build` clones the values of the fields and leaves the builder as it is.
+fn builder_attr_clone_build(input: &DeriveInput) -> bool {
+    find_builder_attr_path(&input.attrs, "clone_build").is_some()
+}
+
+/// Returns `T: Clone` for every type parameter of the struct as
+/// `derive(Clone)` does, for the code cloning the values of the fields.
+fn clone_bounds(input: &DeriveInput) -> Vec<syn::WherePredicate> {
+    input
+        .generics
+        .type_params()
+        .map(|param| {
+            let ident = &param.ident;
+            syn::parse_quote!(#ident: ::std::clone::Clone)
+        })
+        .collect()
+}
+
+/// Returns the where clause of the build functions, such as
+/// `where T: Clone`, only with `#[builder(clone_build)]`. It is put on
+/// the functions rather than the impls so that the other methods do
+/// not require it.
+fn build_fn_where_clause(input: &DeriveInput) -> TokenStream {
+    if !builder_attr_clone_build(input) {
+        return TokenStream::new();
+    }
+    let bounds = clone_bounds(input);
+    quote! { where #(#bounds),* }
+}
+
+/// Look for `#[builder(try_from)]` attribute on the struct.
+fn builder_attr_try_from(input: &DeriveInput) -> bool {
+    find_builder_attr_path(&input.attrs, "try_from").is_some()
+}
+
+/// Look for `#[builder(from_self)]` attribute on the struct.
+fn builder_attr_from_self(input: &DeriveInput) -> bool {
+    find_builder_attr_path(&input.attrs, "from_self").is_some()
+}
+
+/// Look for `#[builder(template)]` attribute on the struct, with which
+/// the builder has `build_from_template` taking the unset fields from
+/// a template.
+fn builder_attr_template(input: &DeriveInput) -> bool {
+    find_builder_attr_path(&input.attrs, "template").is_some()
+}
+
+/// Look for `#[builder(mutators)]` attribute on the struct, with which
+/// the builder has `map_{field}` methods adjusting the values set.
+fn builder_attr_mutators(input: &DeriveInput) -> bool {
+    find_builder_attr_path(&input.attrs, "mutators").is_some()
+}
+
+/// Returns whether the field has the method of `#[builder(mutators)]`,
+/// which is the field with a setter other than a sub-builder.
+fn has_mutator(input: &DeriveInput, field: &syn::Field) -> bool {
+    builder_attr_mutators(input)
+        && !builder_attr_setter_skip(field)
+        && !builder_attr_sub_builder(field)
+}
+
+/// Returns the name of the method of `#[builder(mutators)]`, which is
+/// `map_{rename or field name}`.
+fn mutator_fn_name(field: &syn::Field) -> syn::Ident {
+    let name = renamed_field_name(field);
+    format_ident!("map_{}", name.unraw(), span = name.span())
+}
+
+/// Look for `#[builder(merge)]` attribute on the struct.
+fn builder_attr_merge(input: &DeriveInput) -> bool {
+    find_builder_attr_path(&input.attrs, "merge").is_some()
+}
+
+/// Look for `#[builder(build_wrappers)]` attribute on the struct, with
+/// which the builder has `build_arc` and `build_boxed`.
+fn builder_attr_build_wrappers(input: &DeriveInput) -> bool {
+    find_builder_attr_path(&input.attrs, "build_wrappers").is_some()
+}
+
+/// Returns the names of the methods generated by
+/// `#[builder(build_wrappers)]` and the pointers they build into, such as
+/// `(build_arc, "Arc")`, which follow the name of the build function.
+fn build_wrapper_fn_names(input: &DeriveInput) -> Vec<(syn::Ident, &'static str)> {
+    if !builder_attr_build_wrappers(input) {
+        return Vec::new();
+    }
+    let (build_fn_name, _) = builder_attr_build_fn(input)
+        .unwrap_or_else(|_| (format_ident!("build"), builder_item_vis(input)));
+    let name = build_fn_name.unraw();
+    vec![
+        (format_ident!("{}_arc", name), "Arc"),
+        (format_ident!("{}_boxed", name), "Box"),
+    ]
+}
+
+/// Look for `#[builder(panic_helper)]` attribute on the struct.
+fn builder_attr_panic_helper(input: &DeriveInput) -> bool {
+    get_builder_meta_items(&input.attrs).any(|meta| match meta {
+        syn::NestedMeta::Meta(syn::Meta::Path(ref path)) => is_path_eq(path, "panic_helper"),
+        _ => false,
+    })
+}
+
+/// Look for `#[builder(error_derive = "thiserror")]` attribute on the
+/// struct.
+fn builder_attr_error_derive_thiserror(input: &DeriveInput) -> bool {
+    matches!(
+        find_builder_attr_str(&input.attrs, "error_derive"),
+        Some(s) if s.value() == "thiserror"
+    )
+}
+
+/// Check that the value of `#[builder(error_derive = "...")]` is the
+/// supported one.
+fn validate_error_derive(input: &DeriveInput) -> Result<(), syn::Error> {
+    match find_builder_attr_str(&input.attrs, "error_derive") {
+        Some(s) if s.value() != "thiserror" => Err(syn::Error::new_spanned(
+            s,
+            "expected `error_derive = \"thiserror\"`",
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Look for `#[builder(on_missing = "default")]` attribute on the struct,
+/// which builds every unset required field from its `Default`.
+fn builder_attr_on_missing_default(input: &DeriveInput) -> bool {
+    matches!(
+        find_builder_attr_str(&input.attrs, "on_missing"),
+        Some(s) if s.value() == "default"
+    )
+}
+
+/// Check that the value of `#[builder(on_missing = "...")]` is either
+/// `"default"` or `"error"`.
+fn validate_on_missing(input: &DeriveInput) -> Result<(), syn::Error> {
+    match find_builder_attr_str(&input.attrs, "on_missing") {
+        Some(s) if s.value() != "default" && s.value() != "error" => Err(syn::Error::new_spanned(
+            s,
+            "expected `on_missing = \"default\"` or `on_missing = \"error\"`",
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Look for `#[builder(fallible)]` attribute on the struct.
+fn builder_attr_fallible(input: &DeriveInput) -> bool {
+    find_builder_attr_path(&input.attrs, "fallible").is_some()
+}
+
+/// Returns whether `build` never fails, that is, every field is
+/// `Option<T>`, `Vec<T>` or has a default which never fails and no
+/// field is validated or has `#[builder(once = "error")]`.
+/// `build` returns `Result` anyway with `#[builder(error = "...")]`,
+/// `#[builder(error_derive = "...")]` or `#[builder(fallible)]`, so
+/// that a required field is able to be added later without breaking
+/// the callers.
+fn is_infallible_build(input: &DeriveInput) -> bool {
+    !builder_attr_fallible(input)
+        && builder_attr_error(input).is_none()
+        && find_builder_attr_str(&input.attrs, "error_derive").is_none()
+        && !has_once_error(input)
+        && origin_fields(input).all(|field| {
+            !is_required_field(&field)
+                && builder_attr_validate(&field).is_none()
+                && builder_attr_default_try(&field).is_none()
+        })
+}
+
+/// Look for `#[builder(build_fn = "...")]` or
+/// `#[builder(build_fn(name = "...", vis = "..."))]` attribute on the
+/// struct and get the name and visibility of the build function.
+/// `build` with the visibility of the other methods is the default.
+fn builder_attr_build_fn(input: &DeriveInput) -> Result<(syn::Ident, syn::Visibility), syn::Error> {
+    let mut name = format_ident!("build");
+    let mut vis = builder_item_vis(input);
+
+    for meta in get_builder_meta_items(&input.attrs) {
+        match meta {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                ref path,
+                lit: syn::Lit::Str(ref s),
+                ..
+            })) if is_path_eq(path, "build_fn") => {
+                name = s.parse()?;
+            }
+            syn::NestedMeta::Meta(syn::Meta::List(ref list))
+                if is_path_eq(&list.path, "build_fn") =>
+            {
+                for nested in list.nested.iter() {
+                    match nested {
+                        syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                            ref path,
+                            lit: syn::Lit::Str(ref s),
+                            ..
+                        })) if is_path_eq(path, "name") => {
+                            name = s.parse()?;
+                        }
+                        syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                            ref path,
+                            lit: syn::Lit::Str(ref s),
+                            ..
+                        })) if is_path_eq(path, "vis") => {
+                            vis = s.parse()?;
+                        }
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                nested,
+                                "expected `name = \"...\"` or `vis = \"...\"`",
+                            ))
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok((name, vis))
+}
+
+/// Check that `#[builder(prefix = "...")]`, `#[builder(suffix = "...")]`
+/// and `#[builder(each_prefix = "...")]` produce legal identifiers.
+fn validate_setter_affixes(input: &DeriveInput) -> Result<(), syn::Error> {
+    for key in &["prefix", "suffix", "each_prefix"] {
+        let s = match find_builder_attr_str(&input.attrs, key) {
+            Some(s) => s,
+            None => continue,
+        };
+        for field in builder_fields(input) {
+            let name = if *key == "each_prefix" {
+                // an illegal `each` is reported by `validate_fields`.
+                match builder_attr_each_name(&field) {
+                    Some(Ok(each)) => format!("{}{}", s.value(), each.unraw()),
+                    _ => continue,
+                }
+            } else {
+                let name = renamed_field_name(&field).unraw();
+                if *key == "prefix" {
+                    format!("{}{}", s.value(), name)
+                } else {
+                    format!("{}{}", name, s.value())
+                }
+            };
+            if syn::parse_str::<syn::Ident>(&name).is_err() {
+                return Err(syn::Error::new_spanned(
+                    &s,
+                    format!("`{}` is not a valid identifier", name),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Keys of the struct level `#[builder(...)]` attributes.
+const CONTAINER_ATTR_KEYS: &[(&str, AttrShape)] = &[
+    ("name", AttrShape::Str),
+    ("pattern", AttrShape::Str),
+    ("setter", AttrShape::List),
+    ("build_fn", AttrShape::StrOrList),
+    ("module", AttrShape::Str),
+    ("error", AttrShape::Str),
+    ("vis", AttrShape::Str),
+    ("panic_helper", AttrShape::Flag),
+    ("prefix", AttrShape::Str),
+    ("suffix", AttrShape::Str),
+    ("each_prefix", AttrShape::Str),
+    ("entry", AttrShape::Str),
+    ("typestate", AttrShape::Flag),
+    ("build_with", AttrShape::Str),
+    ("fallible", AttrShape::Flag),
+    ("error_derive", AttrShape::Str),
+    ("derive", AttrShape::List),
+    ("debug", AttrShape::Flag),
+    ("merge", AttrShape::Flag),
+    ("no_must_use", AttrShape::Flag),
+    ("on_missing", AttrShape::Str),
+    ("clone_build", AttrShape::Flag),
+    ("try_from", AttrShape::Flag),
+    ("from_self", AttrShape::Flag),
+    ("wrap", AttrShape::Str),
+    ("template", AttrShape::Flag),
+    ("build_wrappers", AttrShape::Flag),
+    ("mutators", AttrShape::Flag),
+];
+
+/// Pairs of the struct level `#[builder(...)]` attributes which are
+/// not able to be set together.
+const CONTAINER_ATTR_CONFLICTS: &[(&str, &str)] =
+    &[("error", "error_derive"), ("wrap", "build_wrappers")];
+
+/// Keys of the struct level `#[builder(setter(...))]` attributes.
+const CONTAINER_SETTER_KEYS: &[(&str, AttrShape)] =
+    &[("into", AttrShape::Flag), ("owned", AttrShape::Flag)];
+
+/// Check the struct level `#[builder(...)]` attributes so that the
+/// other functions are able to assume that they are well-formed.
+fn validate_container_attrs(input: &DeriveInput) -> Result<(), syn::Error> {
+    let mut errors = Vec::new();
+    errors.extend(validate_attr_format(&input.attrs).err());
+    errors.extend(
+        validate_attr_keys(get_builder_meta_lists(&input.attrs), CONTAINER_ATTR_KEYS).err(),
+    );
+    errors.extend(
+        validate_attr_keys(
+            get_builder_setter_lists(&input.attrs),
+            CONTAINER_SETTER_KEYS,
+        )
+        .err(),
+    );
+    errors.extend(validate_attr_conflicts(&input.attrs, CONTAINER_ATTR_CONFLICTS).err());
+    errors.extend(validate_error_derive(input).err());
+    errors.extend(validate_on_missing(input).err());
+    errors.extend(builder_attr_name(input).and_then(Result::err));
+    errors.extend(builder_attr_vis(input).and_then(Result::err));
+    errors.extend(builder_attr_module(input).and_then(Result::err));
+    errors.extend(builder_attr_error(input).and_then(Result::err));
+    errors.extend(builder_attr_build_with(input).and_then(Result::err));
+    errors.extend(builder_attr_wrap(input).and_then(Result::err));
+    errors.extend(builder_attr_entry(input).err());
+    errors.extend(builder_attr_pattern(input).err());
+    errors.extend(builder_attr_derive(input).err());
+    errors.extend(validate_builder_debug(input).err());
+    errors.extend(builder_attr_build_fn(input).err());
+    errors.extend(validate_setter_affixes(input).err());
+    errors.extend(typestate::validate(input).err());
+    combine_errors(errors).map_or(Ok(()), Err)
+}
+
+/// Check that no key of `#[builder(...)]` attributes is given twice,
+/// even across several `#[builder(...)]`, and that no pair of
+/// `conflicts` is given together. The error points at the latter one.
+fn validate_attr_conflicts(
+    attrs: &[syn::Attribute],
+    conflicts: &[(&str, &str)],
+) -> Result<(), syn::Error> {
+    let mut seen: Vec<syn::Path> = Vec::new();
+    for meta in get_builder_meta_items(attrs) {
+        let path = match meta {
+            syn::NestedMeta::Meta(ref meta) => meta.path().clone(),
+            syn::NestedMeta::Lit(_) => continue,
+        };
+        let key = quote! { #path }.to_string().replace(' ', "");
+        if seen.contains(&path) {
+            return Err(syn::Error::new_spanned(
+                meta,
+                format!("duplicate builder attribute `{}`", key),
+            ));
+        }
+        // a key is able to be in several pairs, such as `default`.
+        let conflict = conflicts
+            .iter()
+            .filter_map(|(a, b)| {
+                if is_path_eq(&path, a) {
+                    Some(b)
+                } else if is_path_eq(&path, b) {
+                    Some(a)
+                } else {
+                    None
+                }
+            })
+            .find(|other| seen.iter().any(|p| is_path_eq(p, other)));
+        if let Some(other) = conflict {
+            return Err(syn::Error::new_spanned(
+                meta,
+                format!(
+                    "builder attribute `{}` is not able to be used with `{}`",
+                    key, other
+                ),
+            ));
+        }
+        seen.push(path);
+    }
+    Ok(())
+}
+
+/// Check that every key in `lists`, which are `builder(...)` or
+/// `setter(...)`, is one of `keys`. The error points at the unknown
+/// key and names it, such as "unknown builder attribute `defualt`,
+/// did you mean `default`?" if one of `keys` is close to it.
+/// A known key of the wrong shape, such as `each(arg)`, is reported
+/// at the key instead of being ignored.
+///
+/// A misspelling of `each`, such as `eac = "arg"`, is reported as
+/// "expected `builder(each = \"...\")`" at the misspelled key-value
+/// instead, as the workshop's test expects.
+fn validate_attr_keys(
+    lists: impl Iterator<Item = syn::MetaList>,
+    keys: &[(&str, AttrShape)],
+) -> Result<(), syn::Error> {
+    let mut errors = Vec::new();
+    for list in lists {
+        let kind = &list.path;
+        let kind = quote! { #kind }.to_string().replace(' ', "");
+        for meta in list.nested.iter() {
+            let meta = match meta {
+                syn::NestedMeta::Meta(ref meta) => meta,
+                syn::NestedMeta::Lit(ref lit) => {
+                    errors.push(syn::Error::new_spanned(
+                        lit,
+                        format!("expected a {} attribute, found a literal", kind),
+                    ));
+                    continue;
+                }
+            };
+            let path = meta.path();
+            if let Some((key, shape)) = keys.iter().find(|(key, _)| is_path_eq(path, key)) {
+                if !shape.accepts(meta) {
+                    errors.push(syn::Error::new_spanned(meta, shape.expected(key)));
+                }
+                continue;
+            }
+            let name = quote! { #path }.to_string().replace(' ', "");
+            let error = match similar_key(&name, keys) {
+                Some("each") if kind == "builder" => {
+                    syn::Error::new_spanned(meta, "expected `builder(each = \"...\")`")
+                }
+                Some(similar) => syn::Error::new_spanned(
+                    path,
+                    format!(
+                        "unknown {} attribute `{}`, did you mean `{}`?",
+                        kind, name, similar
+                    ),
+                ),
+                None => {
+                    syn::Error::new_spanned(path, format!("unknown {} attribute `{}`", kind, name))
+                }
+            };
+            errors.push(error);
+        }
+    }
+    combine_errors(errors).map_or(Ok(()), Err)
+}
+
+/// Returns the key in `keys` which is the closest to `name` if it is
+/// close enough to be a misspelling, such as `each` for `eahc`.
+fn similar_key<'a>(name: &str, keys: &[(&'a str, AttrShape)]) -> Option<&'a str> {
+    let max_distance = std::cmp::max(name.len(), 3) / 3;
+    keys.iter()
+        .map(|(key, _)| (edit_distance(name, key), *key))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, key)| key)
+}
+
+/// Returns the number of the insertions, deletions, substitutions and
+/// transpositions of adjacent characters turning `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // `d[i][j]` is the distance between `a[..i]` and `b[..j]`.
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// This function wraps `builder`, the builder struct and its impls,
+/// in a module if `#[builder(module = "...")]` is given, such as
+/// ```ignore
+/// pub mod command_builder {
+///     use super::*;
+///
+///     pub struct Builder { ... }
+///
+///     #[derive(Debug)]
+///     pub struct BuildError { ... }
+///
+///     ...
+/// }
+///
+/// pub use self::command_builder::Builder as CommandBuilder;
+/// ```
+///
+/// so that the builder is named from other modules as
+/// `command_builder::Builder` and its error as
+/// `command_builder::BuildError`.
+/// Both the module and the re-export have the visibility of the builder.
+///
+/// Note that the module is not able to be used for a struct defined
+/// in a function body, because `use super::*` does not import the
+/// items of the function. Without the module, the builder is put next
+/// to the struct and works in a function body as well.
+fn ts_builder_module(input: &DeriveInput, builder: TokenStream) -> TokenStream {
+    let module = match builder_attr_module(input) {
+        Some(Ok(module)) => module,
+        _ => return builder,
+    };
+    let builder_name = builder_name(input);
+    let builder_export_name = builder_export_name(input);
+    let vis = builder_vis(input);
+    let export = if builder_name == builder_export_name {
+        quote! { #vis use self::#module::#builder_name; }
+    } else {
+        quote! { #vis use self::#module::#builder_name as #builder_export_name; }
+    };
+
+    let doc = ts_doc(format!("The builder of `{}`.", origin_name(input)));
+
+    quote! {
+        #doc
+        #vis mod #module {
+            use super::*;
+
+            #builder
+        }
+
+        #export
+    }
+}
+
+/// Returns `#[must_use]` attribute for the methods creating the
+/// builder, such as `builder` and `new`, unless
+/// `#[builder(no_must_use)]` is given.
+fn ts_must_use_builder(input: &DeriveInput) -> TokenStream {
+    if builder_attr_no_must_use(input) {
+        return TokenStream::new();
+    }
+    quote! { #[must_use = "the builder does nothing unless `build` is called"] }
+}
+
+/// Returns `#[must_use]` attribute for the setters and the other methods
+/// returning the builder they are called on, only with the owned pattern,
+/// which drops the change unless the returned builder is used. The ones
+/// taking `&mut self` have already written the change into the builder,
+/// so that they are able to be called as statements.
+fn ts_must_use_setter(input: &DeriveInput) -> TokenStream {
+    match builder_pattern(input) {
+        BuilderPattern::Owned => ts_must_use_builder(input),
+        BuilderPattern::Mutable => TokenStream::new(),
+    }
+}
+
+/// Returns `#[must_use]` attribute for `build` returning the struct,
+/// unless `#[builder(no_must_use)]` is given. `build` returning
+/// `Result` does not need it.
+fn ts_must_use_build(input: &DeriveInput) -> TokenStream {
+    if builder_attr_no_must_use(input) {
+        return TokenStream::new();
+    }
+    quote! { #[must_use] }
+}
+
+/// Returns `#[doc = "..."]` attribute for a generated item. The doc is
+/// given to every public item so that `#![deny(missing_docs)]` in the
+/// user's crate is satisfied.
+fn ts_doc(doc: String) -> TokenStream {
+    quote! { #[doc = #doc] }
+}
+
+/// Returns the name of the field to be shown in the doc of a generated
+/// item or in an error, such as `type` for `r#type`.
+fn field_display_name(field: &syn::Field) -> String {
+    field.ident.as_ref().unwrap().unraw().to_string()
+}
+
+/// This function returns `TokenStream` which represents
+/// a code such as
+/// ```ignore
+/// impl Command {
+///     pub fn builder() -> CommandBuilder {
+///         CommandBuilder::new()
+///     }
+/// }
+/// ```
+///
+/// where `builder` is able to be renamed by `#[builder(entry = "...")]`.
+/// It has the visibility of the builder and returns
+/// `command_builder::Builder` with `#[builder(module = "command_builder")]`.
+/// It takes the fields with `#[builder(ctor_args)]` as `new` does.
+fn ts_origin_impl_builder_fn(input: &DeriveInput) -> TokenStream {
+    if enums::origin_variant(input).is_some() {
+        // the builder of the variant is created by `enums::ts_selector`.
+        return TokenStream::new();
+    }
+    let origin_name = origin_name(input);
+    let builder_name = builder_name(input);
+    let builder_path = match builder_attr_module(input) {
+        Some(Ok(module)) => quote! { #module::#builder_name },
+        _ => quote! { #builder_name },
+    };
+    let vis = builder_vis(input);
+    let entry = builder_attr_entry(input).unwrap_or_else(|_| format_ident!("builder"));
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let builder_ty_generics = initial_builder_ty_generics(input);
+    let doc = ts_doc(format!("Creates a builder of `{}`.", origin_name));
+    let must_use = ts_must_use_builder(input);
+    let params = ctor_params(input);
+    let args = ctor_args_fields(input)
+        .into_iter()
+        .map(|field| field.ident.unwrap());
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #origin_name #ty_generics #where_clause {
+            #doc
+            #must_use
+            #vis fn #entry(#(#params),*) -> #builder_path #builder_ty_generics {
+                #builder_path::new(#(#args),*)
+            }
+        }
+    }
+}
+
+/// This function returns `TokenStream` which represents
+/// a code such as
+///
+/// ```ignore
+/// pub struct CommandBuilder {
+///     executable: Option<String>,
+///     args: Vec<String>,
+///     // optional field
+///     current_dir: Option<String>,
+///     __set: [bool; 3],
+/// }
+/// ```
+///
+/// Note that original `Command` struct is such as
+///
+/// ```ignore
+/// pub struct Command {
+///     executable: String,
+///     // multiple value field
+///     args: Vec<String>,
+///     // optional field
+///     current_dir: Option<String>,
+/// }
+/// ```
+///
+/// A field is `None` until it is set, while a collection is held as it
+/// is so that the defaults reading the builder see the collection
+/// itself. The flag of each field in `__set` tells whether it is set,
+/// which `build`, the defaults, `missing_fields` and `merge` read, so
+/// that a field set to be `None` or empty is told from the one which is
+/// never set. See `set_flags_name`.
+///
+/// The builder has the same visibility as the original struct unless
+/// `#[builder(vis = "...")]` is given.
+///
+/// With `#[builder(derive(Clone, Debug))]`, the builder struct gets
+/// `#[derive(Clone, Debug)]`. Whether the fields implement them is
+/// left to the derives, whose errors point at the original fields.
+fn ts_builder_struct(input: &DeriveInput) -> TokenStream {
+    let builder_name = builder_name(input);
+    let generics = builder_generics(input);
+    let where_clause = &generics.where_clause;
+    let set_flags = match builder_fields(input).count() {
+        0 => quote! {},
+        count => {
+            let set_flags = set_flags_name();
+            quote! { #set_flags: [bool; #count], }
+        }
+    };
+    let builder_fields: TokenStream = builder_fields(input)
+        .map(|field| {
+            let name = field.ident.as_ref().unwrap();
+            let cfgs = field_cfg_attrs(&field);
+            // the errors of the derives given by `#[builder(derive(...))]`
+            // point at the original field.
+            let span = field.span();
+            if let Some(ty) = optional_type_of(&field) {
+                quote_spanned! {span=>
+                    #(#cfgs)*
+                    #name: ::std::option::Option<#ty>,
+                }
+            } else if let Some(ty) = single_generic_type_of(&field, "Vec") {
+                quote_spanned! {span=>
+                    #(#cfgs)*
+                    #name: ::std::vec::Vec<#ty>,
+                }
+            } else if is_collection_field(&field) {
+                let ty = &field.ty;
+                quote_spanned! {span=>
+                    #(#cfgs)*
+                    #name: #ty,
+                }
+            } else if let Some(ty) =
+                sub_builder_type(&field).filter(|_| builder_attr_sub_builder(&field))
+            {
+                quote_spanned! {span=>
+                    #(#cfgs)*
+                    #name: ::std::option::Option<#ty>,
+                }
+            } else {
+                let ty = &field.ty;
+                quote_spanned! {span=>
+                    #(#cfgs)*
+                    #name : ::std::option::Option<#ty>,
+                }
+            }
+        })
+        .collect();
+    let already_set = if has_once_error(input) {
+        let already_set = already_set_name();
+        quote! { #already_set: ::std::option::Option<&'static str>, }
+    } else {
+        quote! {}
+    };
+    let once_set = match once_fields(input).count() {
+        0 => quote! {},
+        count => {
+            let once_set = once_set_name();
+            quote! { #once_set: [bool; #count], }
+        }
+    };
+    let vis = builder_item_vis(input);
+    let doc = ts_doc(format!("The builder of `{}`.", origin_name(input)));
+    let derives = builder_attr_derive(input).unwrap_or_default();
+    let derive = if derives.is_empty() {
+        quote! {}
+    } else {
+        quote! { #[derive(#(#derives),*)] }
+    };
+    quote! {
+        #doc
+        #derive
+        // The fields are not always `Debug`, such as `Box<dyn Fn()>`.
+        #[allow(missing_debug_implementations)]
+        #vis struct #builder_name #generics #where_clause {
+            #builder_fields
+            #set_flags
+            #once_set
+            #already_set
+        }
+    }
+}
+
+/// Returns the fields of the builder which `new` creates, such as
+/// `executable: None, args: Vec::new(), __set: [false, false],`,
+/// where the value of the field with `#[builder(ctor_args)]` is given
+/// by `ctor_value` and the field is set.
+fn ts_builder_initial_fields(
+    input: &DeriveInput,
+    ctor_value: impl Fn(&syn::Ident) -> TokenStream,
+) -> TokenStream {
+    let fields: TokenStream = builder_fields(input)
+        .map(|field| {
+            let name = field.ident.as_ref().unwrap();
+            let cfgs = field_cfg_attrs(&field);
+            let value = if builder_attr_ctor_args(&field) {
+                ctor_value(name)
+            } else if is_collection_field(&field) {
+                ts_new_collection(&field_type(&field))
+            } else {
+                quote! { ::std::option::Option::None }
+            };
+            quote! {
+                #(#cfgs)*
+                #name: #value,
+            }
+        })
+        .collect();
+    let set_flags = ts_set_flags_init(input, builder_attr_ctor_args);
+    let once_set = ts_once_set_init(input);
+    let already_set = ts_already_set_init(input);
+    quote! {
+        #fields
+        #set_flags
+        #once_set
+        #already_set
+    }
+}
+
+/// Returns `__already_set: None,` for the builder which holds the field
+/// of `already_set_name`, or nothing.
+fn ts_already_set_init(input: &DeriveInput) -> TokenStream {
+    if !has_once_error(input) {
+        return TokenStream::new();
+    }
+    let already_set = already_set_name();
+    quote! { #already_set: ::std::option::Option::None, }
+}
+
+///
+/// This function returns `TokenStream` which represents
+/// a code such as
+/// ```ignore
+/// impl CommandBuilder {
+///     pub fn new() -> CommandBuilder {
+///         CommandBuilder {
+///             executable: None,
+///             current_dir: None,
+///         }
+///     }
+/// }
+///
+/// impl Default for CommandBuilder {
+///     fn default() -> CommandBuilder {
+///         CommandBuilder::new()
+///     }
+/// }
+/// ```
+///
+/// where `Default` is implemented for the builder which `new` returns,
+/// that is, `CommandBuilder<false>` with `#[builder(typestate)]`.
+///
+/// The fields with `#[builder(ctor_args)]` are the parameters of `new`
+/// in declaration order, such as `new(executable: String)`, and are
+/// set from the start. Then `Default` is not implemented.
+fn ts_builder_impl_new_fn(input: &DeriveInput) -> TokenStream {
+    let builder_name = builder_name(input);
+    let vis = builder_item_vis(input);
+    let (impl_generics, _, where_clause) = input.generics.split_for_impl();
+    let ty_generics = initial_builder_ty_generics(input);
+    let builder_initial_fields =
+        ts_builder_initial_fields(input, |name| quote! { ::std::option::Option::Some(#name) });
+
+    let params = ctor_params(input);
+    let doc = if params.is_empty() {
+        ts_doc(format!(
+            "Creates a builder of `{}` whose fields are not set.",
+            origin_name(input)
+        ))
+    } else {
+        ts_doc(format!(
+            "Creates a builder of `{}` whose fields are not set \
+             except the ones given as the arguments.",
+            origin_name(input)
+        ))
+    };
+    let must_use = ts_must_use_builder(input);
+    let default = if params.is_empty() {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::std::default::Default for #builder_name #ty_generics #where_clause {
+                fn default() -> #builder_name #ty_generics {
+                    #builder_name::new()
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #builder_name #ty_generics #where_clause {
+            #doc
+            #must_use
+            #vis fn new(#(#params),*) -> #builder_name #ty_generics {
+                #builder_name {
+                    #builder_initial_fields
+                }
+            }
+        }
+
+        #default
+    }
+}
+
+/// This function returns `TokenStream` which represents
+/// a code such as
+/// ```ignore
+/// impl CommandBuilder {
+///     pub const FIELDS: &'static [&'static str] = &["executable", "current_dir"];
+///     pub const REQUIRED_FIELDS: &'static [&'static str] = &["executable"];
+/// }
+/// ```
+///
+/// where the names are the ones of the setters, so that they match
+/// the methods of the builder. The fields with `#[builder(skip)]` or
+/// `#[builder(setter(skip))]` have no setter and are not listed.
+/// As `new`, they are on the builder which `new` returns.
+fn ts_builder_impl_field_consts(input: &DeriveInput) -> TokenStream {
+    let builder_name = builder_name(input);
+    let vis = builder_item_vis(input);
+    let (impl_generics, _, where_clause) = input.generics.split_for_impl();
+    let ty_generics = initial_builder_ty_generics(input);
+    let names = |fields: Vec<syn::Field>| -> Vec<TokenStream> {
+        fields
+            .iter()
+            .filter(|field| !builder_attr_setter_skip(field))
+            .map(|field| {
+                let cfgs = field_cfg_attrs(field);
+                let name = setter_name(input, field).unraw().to_string();
+                quote! { #(#cfgs)* #name }
+            })
+            .collect()
+    };
+    let fields = names(builder_fields(input).collect());
+    let required_fields = names(origin_fields(input).filter(is_required_field).collect());
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #builder_name #ty_generics #where_clause {
+            /// The names of the setters of the fields.
+            #vis const FIELDS: &'static [&'static str] = &[#(#fields),*];
+            /// The names of the setters of the fields which must be set.
+            #vis const REQUIRED_FIELDS: &'static [&'static str] = &[#(#required_fields),*];
+        }
+    }
+}
+
+/// Returns the argument type of the setter of the field and the
+/// expression which converts the argument `item` to the value to
+/// be stored, such as `String` and `item`.
+/// With `#[builder(setter(transform = "..."))]`, it is the type of the
+/// parameter of the closure and the call of it, which takes precedence
+/// over the struct level `#[builder(setter(into))]`.
+fn setter_arg(input: &DeriveInput, field: &syn::Field) -> (TokenStream, TokenStream) {
+    if let Some(Ok((closure, arg_ty))) = builder_attr_setter_transform(field) {
+        let transform = syn::Ident::new("transform", proc_macro2::Span::mixed_site());
+        return (
+            quote! { #arg_ty },
+            quote! {
+                {
+                    let #transform = #closure;
+                    #transform(item)
+                }
+            },
+        );
+    }
+    // `T` when field type is `Option<T>` or `T`.
+    let ty = if is_collection_field(field) {
+        field.ty.clone()
+    } else {
+        optional_type_of(field).unwrap_or_else(|| field.ty.clone())
+    };
+    // `boxed` on the other type is reported by `validate_fields`.
+    let boxed_arg_ty = if builder_attr_boxed(field) {
+        boxed_setter_arg_type(&ty)
+    } else {
+        None
+    };
+    if let Some(arg_ty) = boxed_arg_ty {
+        (arg_ty, quote! { ::std::boxed::Box::new(item) })
+    } else if is_setter_into(input, field) {
+        (
+            quote! { impl ::std::convert::Into<#ty> },
+            quote! { ::std::convert::Into::into(item) },
+        )
+    } else {
+        (quote! { #ty }, quote! { item })
+    }
+}
+
+/// Returns the name of the field of the builder which holds the name
+/// of the first field with `#[builder(once = "error")]` set twice.
+/// It is only in the builder if `has_once_error` returns `true`.
+fn already_set_name() -> syn::Ident {
+    format_ident!("__already_set")
+}
+
+/// Returns the name of the field of the builder which holds whether
+/// each field is set, such as `__set: [bool; 3]`, so that a field never
+/// set is told from the one set to `None` or to an empty collection,
+/// whatever the builder holds. It is only in the builder if the builder
+/// has some field.
+fn set_flags_name() -> syn::Ident {
+    format_ident!("__set")
+}
+
+/// Returns the index of the flag of the field in the field of
+/// `set_flags_name`, which is the position of the field in the builder.
+fn set_flag_index(input: &DeriveInput, field: &syn::Field) -> usize {
+    builder_fields(input)
+        .position(|other| other.ident == field.ident)
+        .unwrap()
+}
+
+/// Returns `__set: [true, false],` whose flags are given by `is_set`
+/// for each field, or nothing for the builder without fields.
+fn ts_set_flags_init(input: &DeriveInput, is_set: impl Fn(&syn::Field) -> bool) -> TokenStream {
+    let flags: Vec<bool> = builder_fields(input).map(|field| is_set(&field)).collect();
+    if flags.is_empty() {
+        return TokenStream::new();
+    }
+    let set_flags = set_flags_name();
+    quote! { #set_flags: [#(#flags),*], }
+}
+
+/// Returns the expression telling whether the field of `builder` is
+/// set, such as `self.__set[0]`.
+fn ts_is_set(input: &DeriveInput, field: &syn::Field, builder: TokenStream) -> TokenStream {
+    let set_flags = set_flags_name();
+    let index = set_flag_index(input, field);
+    quote! { #builder.#set_flags[#index] }
+}
+
+/// Returns the statement marking the field as set, such as
+/// `self.__set[0] = true;`.
+fn ts_mark_set(input: &DeriveInput, field: &syn::Field) -> TokenStream {
+    let set_flags = set_flags_name();
+    let index = set_flag_index(input, field);
+    quote! { self.#set_flags[#index] = true; }
+}
+
+/// Returns the name of the field of the builder which holds whether
+/// the setter of each field with `#[builder(once)]` has been called,
+/// such as `__once_set: [bool; 1]`. Unlike the flags of `set_flags_name`,
+/// the values given by `From` or `to_builder` do not count, so that the
+/// builder made of an existing value is able to change each of them
+/// once. It is only in the builder if some field has `#[builder(once)]`.
+fn once_set_name() -> syn::Ident {
+    format_ident!("__once_set")
+}
+
+/// Returns the fields with `#[builder(once)]` in the order of their
+/// flags in the field of `once_set_name`.
+fn once_fields<'a>(input: &'a DeriveInput) -> impl Iterator<Item = syn::Field> + 'a {
+    builder_fields(input).filter(|field| matches!(builder_attr_once(field), Some(Ok(_))))
+}
+
+/// Returns the index of the flag of the field in the field of
+/// `once_set_name`, or `None` if the field has no `#[builder(once)]`.
+fn once_set_index(input: &DeriveInput, field: &syn::Field) -> Option<usize> {
+    once_fields(input).position(|other| other.ident == field.ident)
+}
+
+/// Returns `__once_set: [false; 1],` for the builder which has some
+/// field with `#[builder(once)]`, or nothing.
+fn ts_once_set_init(input: &DeriveInput) -> TokenStream {
+    let count = once_fields(input).count();
+    if count == 0 {
+        return TokenStream::new();
+    }
+    let once_set = once_set_name();
+    quote! { #once_set: [false; #count], }
+}
+
+/// Returns the statements put at the head of the setter of the field
+/// with `#[builder(once)]`, such as
+/// ```ignore
+/// if self.__once_set[0] {
+///     panic!("field `executable` was already set");
+/// }
+/// self.__once_set[0] = true;
+/// ```
+///
+/// or the one recording the field for `build` with
+/// `#[builder(once = "error")]`, such as
+/// ```ignore
+/// if self.__once_set[0] {
+///     self.__already_set.get_or_insert("executable");
+/// }
+/// self.__once_set[0] = true;
+/// ```
+fn ts_check_once(input: &DeriveInput, field: &syn::Field) -> TokenStream {
+    let index = match once_set_index(input, field) {
+        Some(index) => index,
+        None => return TokenStream::new(),
+    };
+    let once_set = once_set_name();
+    let name_str = field_display_name(field);
+    let on_set = match builder_attr_once(field) {
+        Some(Ok(OnceMode::Error)) => {
+            let already_set = already_set_name();
+            quote! { self.#already_set.get_or_insert(#name_str); }
+        }
+        _ => {
+            let message = format!("field `{}` was already set", name_str);
+            quote! { ::std::panic!(#message); }
+        }
+    };
+    quote! {
+        if self.#once_set[#index] {
+            #on_set
+        }
+        self.#once_set[#index] = true;
+    }
+}
+
+/// This function returns `TokenStream` which represents
+/// a code such as
+/// ```ignore
+/// impl CommandBuilder {
+///     pub fn executable(&mut self, item: String) -> &mut Self {
+///         self.executable = Some(item);
+///         self.__set[0] = true;
+///         self
+///     }
+///
+///     // replaces everything pushed by `arg` so far
+///     pub fn args(&mut self, item: Vec<String>) -> &mut Self {
+///         self.args = item;
+///         self.__set[1] = true;
+///         self
+///     }
+///
+///     pub fn current_dir(&mut self, item: String) -> &mut Self {
+///         self.current_dir = Some(item);
+///         self.__set[2] = true;
+///         self
+///     }
+/// }
+/// ```
+///
+/// A `Vec` field with `#[builder(each = "...")]` keeps this whole-`Vec`
+/// setter alongside the pushing one generated by
+/// `ts_builder_impl_each_field_fn`, unless both would have the same name.
+///
+/// With `#[builder(into)]` on the field or `#[builder(setter(into))]`
+/// on the struct, the setter takes `item: impl Into<String>` instead.
+/// With `#[builder(setter(transform = "|s: &str| s.trim().to_owned()"))]`,
+/// the setter takes `item: &str` and stores the result of the closure.
+/// With `#[builder(flag)]` on `bool` field, the setter takes no argument
+/// and sets `true`, and `set_verbose(item: bool)` is generated as well.
+/// With `#[builder(boxed)]` on `Box<dyn Fn()>` field, the setter takes
+/// `item: impl Fn() + 'static` and boxes it.
+/// With `#[builder(keep_option)]` or `#[builder(required)]` on
+/// `Option<T>` field, the setter takes the whole `Option<T>`.
+/// With `#[builder(sub_builder)]` on `ListenerConfig` field, the setter
+/// takes `f: impl FnOnce(&mut ListenerConfigBuilder)` and passes it the
+/// builder of the field, which is created by `Default` on the first
+/// call and built by `build`. So the builder of the field must not have
+/// `#[builder(ctor_args)]`, which takes the values `Default` does not
+/// know, and the error points at the attribute if it has.
+/// With `#[builder(once)]`, the setter panics if the field has already
+/// been set, and with `#[builder(once = "error")]` the field is recorded
+/// so that `build` returns an error. See `ts_check_once`.
+///
+/// No setter is generated for `#[builder(setter(skip))]` field, whose
+/// value comes from its default, nor for `#[builder(skip)]` field,
+/// which is not in the builder at all.
+///
+/// The name of the setter is able to be changed by `#[builder(rename = "...")]`
+/// on the field and `#[builder(prefix = "...", suffix = "...")]` on the struct.
+fn ts_builder_impl_fields_fn(input: &DeriveInput) -> TokenStream {
+    let builder_name = builder_name(input);
+    let vis = builder_item_vis(input);
+    let generics = builder_generics(input);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (self_ty, ret_ty) = setter_self(input);
+    let must_use = ts_must_use_setter(input);
+    let builder_fn_fields: TokenStream = builder_fields(input)
+        .filter(|field| !builder_attr_setter_skip(field))
+        // the setters which change the type of the builder
+        .filter(|field| {
+            !(typestate::builder_attr_typestate(input)
+                && is_required_field(field)
+                && !builder_attr_ctor_args(field))
+        })
+        .filter(|field| {
+            // #[builder(each = "...")] の値と同じ場合はスキップする
+            match each_fn_name(input, field) {
+                Some(each_fn_name) => {
+                    setter_name(input, field) != each_fn_name || each_item_type(field).is_none()
+                }
+                None => true,
+            }
+        })
+        .map(|field| {
+            let name = field.ident.as_ref().unwrap();
+            let setter_name = setter_name(input, &field);
+            let is_vec = is_collection_field(&field);
+            let (arg_ty, item) = setter_arg(input, &field);
+            let cfgs = field_cfg_attrs(&field);
+            let doc = ts_doc(format!("Sets `{}`.", field_display_name(&field)));
+            let check_once = ts_check_once(input, &field);
+            let mark_set = ts_mark_set(input, &field);
+            if let Some(sub_ty) =
+                sub_builder_type(&field).filter(|_| builder_attr_sub_builder(&field))
+            {
+                let doc = ts_doc(format!(
+                    "Sets `{}` by its builder, which is built by `build`.",
+                    field_display_name(&field)
+                ));
+                // the builder with `#[builder(ctor_args)]` has no `Default`,
+                // which is reported at the attribute.
+                let span = find_builder_attr_path(&field.attrs, "sub_builder")
+                    .map_or_else(proc_macro2::Span::call_site, |path| path.span());
+                let sub_builder = quote_spanned! {span=>
+                    ::std::option::Option::get_or_insert_with(
+                        &mut self.#name,
+                        ::std::default::Default::default,
+                    )
+                };
+                return quote! {
+                    #(#cfgs)*
+                    #doc
+                    #must_use
+                    #vis fn #setter_name(
+                        #self_ty,
+                        f: impl ::std::ops::FnOnce(&mut #sub_ty),
+                    ) -> #ret_ty {
+                        f(#sub_builder);
+                        #mark_set
+                        self
+                    }
+                };
+            }
+            if builder_attr_flag(&field) {
+                let set_fn_name = flag_set_fn_name(&field);
+                let set_doc = ts_doc(format!(
+                    "Sets `{}` to the value.",
+                    field_display_name(&field)
+                ));
+                let doc = ts_doc(format!("Sets `{}` to `true`.", field_display_name(&field)));
+                return quote! {
+                    #(#cfgs)*
+                    #doc
+                    #must_use
+                    #vis fn #setter_name(#self_ty) -> #ret_ty {
+                        #check_once
+                        self.#name = ::std::option::Option::Some(true);
+                        #mark_set
+                        self
+                    }
+
+                    #(#cfgs)*
+                    #set_doc
+                    #must_use
+                    #vis fn #set_fn_name(#self_ty, item: #arg_ty) -> #ret_ty {
+                        #check_once
+                        self.#name = ::std::option::Option::Some(#item);
+                        #mark_set
+                        self
+                    }
+                };
+            }
+            if is_vec {
+                quote! {
+                    #(#cfgs)*
+                    #doc
+                    #must_use
+                    #vis fn #setter_name(#self_ty, item: #arg_ty) -> #ret_ty {
+                        #check_once
+                        self.#name = #item;
+                        #mark_set
+                        self
+                    }
+                }
+            } else {
+                quote! {
+                    #(#cfgs)*
+                    #doc
+                    #must_use
+                    #vis fn #setter_name(#self_ty, item: #arg_ty) -> #ret_ty {
+                        #check_once
+                        self.#name = ::std::option::Option::Some(#item);
+                        #mark_set
+                        self
+                    }
+                }
+            }
+        })
+        .collect();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #builder_name #ty_generics #where_clause {
+            #builder_fn_fields
+        }
+    }
+}
+
+/// This function returns `TokenStream` which represents
+/// a code such as
+/// ```ignore
+/// impl CommandBuilder {
+///     pub fn arg(&mut self, item: String) -> &mut Self {
+///         self.args.push(item);
+///         self.__set[1] = true;
+///         self
+///     }
+///
+///     pub fn args_all(&mut self, items: impl IntoIterator<Item = String>) -> &mut Self {
+///         self.args.extend(items);
+///         self.__set[1] = true;
+///         self
+///     }
+/// }
+/// ```
+///
+/// for `#[builder(each = "arg")] args: Vec<String>`.
+///
+/// For `Option<Vec<T>>` field, the `Vec` is created on the first push
+/// by `self.args.get_or_insert_with(|| Vec::new())`.
+///
+/// With `#[builder(via = "add")]` on a collection such as `Bag<T>`,
+/// the items are added by `add` instead of `push`, and the field of
+/// the builder starts with `Bag::new()`.
+/// With `#[builder(collection = "im::Vector")]`, the field starts
+/// with `im::Vector::new()` and the items are added by `Extend`.
+///
+/// With `#[builder(into)]` or `#[builder(setter(into))]`, the method
+/// takes `item: impl Into<T>` as the setters do, and the bulk method
+/// takes the items of `impl Into<T>`. It does not change the methods
+/// of a map.
+///
+/// With `#[builder(map)]` on a map such as `HashMap<K, V>` or
+/// `BTreeMap<K, V>`, the method
+/// takes `key: K, value: V` and calls `insert(key, value)`, and the
+/// bulk method takes the items of `(K, V)`.
+///
+/// The value of `#[builder(each_prefix = "...")]` on the struct is
+/// prepended to the name of the method.
+fn ts_builder_impl_each_field_fn(input: &DeriveInput) -> TokenStream {
+    let builder_name = builder_name(input);
+    let vis = builder_item_vis(input);
+    let generics = builder_generics(input);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (self_ty, ret_ty) = setter_self(input);
+    let must_use = ts_must_use_setter(input);
+    let builder_funcs: TokenStream = builder_fields(input)
+        .filter_map(|field| match each_fn_name(input, &field) {
+            Some(each_fn_name) => {
+                let name = field.ident.as_ref().unwrap();
+                // a field of the other type is reported by `validate_fields`.
+                let (ty, is_optional) = each_item_type(&field)?;
+                let collection = if is_optional {
+                    let new = ts_new_collection(&optional_type_of(&field)?);
+                    quote! { self.#name.get_or_insert_with(|| #new) }
+                } else {
+                    quote! { &mut self.#name }
+                };
+                let via = match builder_attr_via(&field) {
+                    Some(via) => Some(via.ok()?),
+                    None => None,
+                };
+                let each_all_fn_name = each_all_fn_name(input, &field)?;
+                let mark_set = ts_mark_set(input, &field);
+                let cfgs = field_cfg_attrs(&field);
+                let field_name = field_display_name(&field);
+                let each_doc = ts_doc(format!("Adds an item to `{}`.", field_name));
+                let each_all_doc = ts_doc(format!("Adds the items to `{}`.", field_name));
+
+                if builder_attr_map(&field) {
+                    let insert = via.unwrap_or_else(|| format_ident!("insert"));
+                    let (key_ty, value_ty) = match ty {
+                        syn::Type::Tuple(ref tuple) => (&tuple.elems[0], &tuple.elems[1]),
+                        _ => unreachable!(),
+                    };
+                    return Some(quote! {
+                        #(#cfgs)*
+                        #each_doc
+                        #must_use
+                        #vis fn #each_fn_name(#self_ty, key: #key_ty, value: #value_ty) -> #ret_ty {
+                            let collection = #collection;
+                            collection.#insert(key, value);
+                            #mark_set
+                            self
+                        }
+
+                        #(#cfgs)*
+                        #each_all_doc
+                        #must_use
+                        #vis fn #each_all_fn_name(
+                            #self_ty,
+                            items: impl ::std::iter::IntoIterator<Item = #ty>,
+                        ) -> #ret_ty {
+                            let collection = #collection;
+                            for (key, value) in items {
+                                collection.#insert(key, value);
+                            }
+                            #mark_set
+                            self
+                        }
+                    });
+                }
+
+                // `into` converts each item, which `each_all` takes as
+                // `impl IntoIterator<Item = impl Into<T>>`.
+                let (item_ty, item, items) = if is_setter_into(input, &field) {
+                    (
+                        quote! { impl ::std::convert::Into<#ty> },
+                        quote! { ::std::convert::Into::<#ty>::into(item) },
+                        quote! {
+                            ::std::iter::Iterator::map(
+                                ::std::iter::IntoIterator::into_iter(items),
+                                ::std::convert::Into::<#ty>::into,
+                            )
+                        },
+                    )
+                } else {
+                    (quote! { #ty }, quote! { item }, quote! { items })
+                };
+                let (push, extend) = match via {
+                    Some(via) => (
+                        quote! { collection.#via(#item); },
+                        quote! {
+                            for item in #items {
+                                collection.#via(item);
+                            }
+                        },
+                    ),
+                    None if is_custom_collection(&field) => (
+                        quote! {
+                            ::std::iter::Extend::extend(collection, ::std::iter::once(#item));
+                        },
+                        quote! { ::std::iter::Extend::extend(collection, #items); },
+                    ),
+                    None => (
+                        quote! { collection.push(#item); },
+                        quote! { ::std::iter::Extend::extend(collection, #items); },
+                    ),
+                };
+
+                let ts = quote! {
+                    #(#cfgs)*
+                    #each_doc
+                    #must_use
+                    #vis fn #each_fn_name(#self_ty, item: #item_ty) -> #ret_ty {
+                        let collection = #collection;
+                        #push
+                        #mark_set
+                        self
+                    }
+
+                    #(#cfgs)*
+                    #each_all_doc
+                    #must_use
+                    #vis fn #each_all_fn_name(
+                        #self_ty,
+                        items: impl ::std::iter::IntoIterator<Item = #item_ty>,
+                    ) -> #ret_ty {
+                        let collection = #collection;
+                        #extend
+                        #mark_set
+                        self
+                    }
+                };
+                Some(ts)
+            }
             None => None,
         })
         .collect();
 
     quote! {
-        impl #builder_name {
-            #builder_funcs
+        #[automatically_derived]
+        impl #impl_generics #builder_name #ty_generics #where_clause {
+            #builder_funcs
+        }
+    }
+}
+
+/// This function returns `TokenStream` which represents
+/// a code such as
+/// ```ignore
+/// impl Extend<String> for CommandBuilder {
+///     fn extend<I: IntoIterator<Item = String>>(&mut self, items: I) {
+///         Extend::extend(&mut self.args, items);
+///         self.__set[1] = true;
+///     }
+/// }
+/// ```
+///
+/// only if exactly one field has `#[builder(each = "...")]`, so that
+/// it is clear which collection the items are added to. The items are
+/// added by the same method as the `each` method, and they are `(K, V)`
+/// for a field with `#[builder(map)]`.
+fn ts_builder_impl_extend(input: &DeriveInput) -> TokenStream {
+    let mut each_fields = builder_fields(input).filter(|field| builder_attr_each(field).is_some());
+    let field = match (each_fields.next(), each_fields.next()) {
+        (Some(field), None) => field,
+        _ => return TokenStream::new(),
+    };
+    // a field of the other type is reported by `validate_fields`.
+    let (ty, is_optional) = match each_item_type(&field) {
+        Some(item) => item,
+        None => return TokenStream::new(),
+    };
+    let via = match builder_attr_via(&field) {
+        Some(Ok(via)) => Some(via),
+        Some(Err(_)) => return TokenStream::new(),
+        None => None,
+    };
+    let builder_name = builder_name(input);
+    let generics = builder_generics(input);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let name = field.ident.as_ref().unwrap();
+    let cfgs = field_cfg_attrs(&field);
+    let collection = format_ident!("collection", span = proc_macro2::Span::mixed_site());
+    let mark_set = ts_mark_set(input, &field);
+    let new_collection = if is_optional {
+        let new = ts_new_collection(&optional_type_of(&field).unwrap());
+        quote! { self.#name.get_or_insert_with(|| #new) }
+    } else {
+        quote! { &mut self.#name }
+    };
+    let extend = if builder_attr_map(&field) {
+        let insert = via.unwrap_or_else(|| format_ident!("insert"));
+        quote! {
+            for (key, value) in items {
+                #collection.#insert(key, value);
+            }
+        }
+    } else {
+        match via {
+            Some(via) => quote! {
+                for item in items {
+                    #collection.#via(item);
+                }
+            },
+            None => quote! { ::std::iter::Extend::extend(#collection, items); },
+        }
+    };
+
+    quote! {
+        #(#cfgs)*
+        #[automatically_derived]
+        impl #impl_generics ::std::iter::Extend<#ty> for #builder_name #ty_generics #where_clause {
+            fn extend<I: ::std::iter::IntoIterator<Item = #ty>>(&mut self, items: I) {
+                let #collection = #new_collection;
+                #extend
+                #mark_set
+            }
+        }
+    }
+}
+
+/// This function produce TokenStream which represents
+/// some source code such as
+/// ```ignore
+/// #[derive(Debug)]
+/// pub enum CommandBuilderError {
+///     UninitializedFields { names: Vec<&'static str> },
+///     ValidationError { field: Option<&'static str>, message: String },
+/// }
+///
+/// impl CommandBuilder {
+///     pub fn build(&mut self) -> Result<Command, CommandBuilderError> {
+///         let mut missing = Vec::new();
+///         if !self.__set[0] {
+///             missing.push("executable");
+///         }
+///         if !missing.is_empty() {
+///             return Err(CommandBuilderError::UninitializedFields { names: missing });
+///         }
+///         Ok(Command {
+///             executable: self
+///                 .executable
+///                 .take()
+///                 .ok_or_else(|| CommandBuilderError::UninitializedFields { names: vec!["executable"] })?,
+///             // `current_dir` is optional field
+///             current_dir: self
+///                 .current_dir
+///                 .take(),
+///         })
+///     }
+/// }
+/// ```
+///
+/// With `#[builder(pattern = "owned")]`, `build` consumes the builder
+/// and moves the fields out of it instead of taking them.
+/// With `#[builder(clone_build)]`, `build` takes `&self` and clones
+/// them, so that the same builder is able to build several times.
+/// With `#[builder(keep)]` on a field, only the value of the field is
+/// cloned and stays in the builder after `build`.
+///
+/// The field with `#[builder(sub_builder)]` is built by its builder
+/// here, whose error is returned as `InitError` of the field. The
+/// `build` of the sub-builder must return `Result`, which
+/// `#[builder(fallible)]` makes so for the builder never failing.
+/// With the mutable pattern it is built in place, so that its `build`
+/// must take `&mut self` or `&self`. See `builds_sub_builder_in_place`.
+///
+/// The name and visibility of `build` are able to be changed by
+/// `#[builder(build_fn(name = "...", vis = "..."))]`.
+/// With `#[builder(wrap = "Arc")]`, `build` returns `Arc<Command>`
+/// instead of `Command`, and so do `try_build` and `build_or_panic`.
+///
+/// Every step which is able to fail is run before any value is taken
+/// out, so that the builder is left as it is if `build` fails and is
+/// able to be fixed and built again. The required fields are checked
+/// first, then `#[builder(default_try = ...)]` is evaluated and the
+/// values are checked by `#[builder(validate = "...")]` in the order of
+/// the fields, borrowing them from the builder. See `ts_check_valid`.
+/// The sub-builders are built at last, and the ones built before the
+/// failing one are put back by `From`. See `ts_restore_sub_builder`.
+/// The defaults reading the builder by `self` are evaluated after the
+/// check and before any value is taken out as well. See
+/// `ts_builder_defaults`.
+///
+/// `CommandBuilderError` is named after the builder so that the
+/// structs in the same module are able to derive `Builder`. It is an
+/// enum so that the caller is able to tell the kind of the failure,
+/// `UninitializedFields` with the names of all the fields which are
+/// not set, or `ValidationError` with the field rejected by
+/// `#[builder(validate = "...")]` and the message. The first field with
+/// `#[builder(once = "error")]` set twice is reported as
+/// `ValidationError` as well.
+/// It implements `Display` such as
+/// "fields `executable`, `program` were not set" and
+/// `std::error::Error` so that it is able to be converted into
+/// `Box<dyn Error>` by `?`.
+///
+/// If no field is able to be missing nor invalid, `build` returns
+/// `Command` itself and `CommandBuilderError` is not generated, unless
+/// `#[builder(fallible)]` is given. See `is_infallible_build`.
+///
+/// With `#[builder(error = "ConfigError")]`, `CommandBuilderError` is not
+/// generated and `build` returns `Result<Command, ConfigError>`.
+/// `ConfigError` must have an associated function
+/// `fn missing_field(field: &'static str) -> ConfigError`, which is
+/// called with the name of the first required field which is not set,
+/// and `fn invalid_field(field: &'static str, message: String) -> ConfigError`
+/// if some field has `#[builder(validate = "...")]` or
+/// `#[builder(once = "error")]`. They play the
+/// role of the conversion from the variants of `CommandBuilderError`,
+/// since a proc-macro crate is not able to export a trait for it.
+fn ts_builder_impl_build_fn(input: &DeriveInput) -> TokenStream {
+    if typestate::builder_attr_typestate(input) {
+        return TokenStream::new();
+    }
+    let origin_name = origin_name(input);
+    let builder_name = builder_name(input);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let error = match builder_attr_error(input) {
+        Some(Ok(error)) => Some(error),
+        _ => None,
+    };
+    let report = |error: TokenStream| quote! { return ::std::result::Result::Err(#error); };
+    // in the order of the fields, so that the first one at fault is reported.
+    let check_valid: TokenStream = origin_fields(input)
+        .filter(|field| !builder_attr_sub_builder(field))
+        .map(|field| {
+            let default_try = ts_default_try_local(input, &field, None);
+            let check_valid = ts_check_valid(input, &field, report);
+            quote! {
+                #default_try
+                #check_valid
+            }
+        })
+        .collect();
+    // built after everything else is checked, and put back if a later
+    // one fails.
+    let sub_builders: Vec<syn::Field> = origin_fields(input)
+        .filter(builder_attr_sub_builder)
+        .collect();
+    let build_sub_builders: TokenStream = sub_builders
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let built = built_local_name(field);
+            let cfgs = field_cfg_attrs(field);
+            let value = ts_build_sub_builder_field(input, field);
+            let init = ts_init_field_error(input, field);
+            let missing = ts_missing_field_error(input, field);
+            let restore: TokenStream = sub_builders[..i]
+                .iter()
+                .map(|field| ts_restore_sub_builder(input, field))
+                .collect();
+            let check_valid = match builder_attr_validate(field) {
+                Some(Ok(validate)) => {
+                    let invalid = ts_invalid_field_error(input, field);
+                    let restore: TokenStream = sub_builders[..=i]
+                        .iter()
+                        .map(|field| ts_restore_sub_builder(input, field))
+                        .collect();
+                    quote! {
+                        #(#cfgs)*
+                        if let ::std::result::Result::Err(message) = #validate(&#built) {
+                            #restore
+                            return ::std::result::Result::Err(#invalid);
+                        }
+                    }
+                }
+                _ => TokenStream::new(),
+            };
+            quote! {
+                #(#cfgs)*
+                let #built = match #value {
+                    ::std::option::Option::Some(::std::result::Result::Ok(value)) => value,
+                    ::std::option::Option::Some(::std::result::Result::Err(source)) => {
+                        #restore
+                        return ::std::result::Result::Err(#init);
+                    }
+                    ::std::option::Option::None => {
+                        #restore
+                        return ::std::result::Result::Err(#missing);
+                    }
+                };
+                #check_valid
+            }
+        })
+        .collect();
+    let values = ts_build_values(input);
+    let origin = ts_construct_origin(input, values);
+    let defaults = ts_builder_defaults(input);
+    let built_ty = ts_built_type(input);
+    let build_error = build_error_name(input);
+    // not to collide with the names in the expressions of the defaults.
+    let missing = syn::Ident::new("missing", proc_macro2::Span::mixed_site());
+    let check_missing = match error {
+        Some(_) => origin_fields(input)
+            .filter(is_required_field)
+            .map(|field| {
+                let is_set = ts_is_set(input, &field, quote! { self });
+                let cfgs = field_cfg_attrs(&field);
+                let error = ts_missing_field_error(input, &field);
+                quote! {
+                    #(#cfgs)*
+                    if !#is_set {
+                        return ::std::result::Result::Err(#error);
+                    }
+                }
+            })
+            .collect(),
+        None => {
+            let collect_missing = ts_collect_missing(input, &missing);
+            quote! {
+                #collect_missing
+                if !#missing.is_empty() {
+                    return ::std::result::Result::Err(#build_error::UninitializedFields {
+                        names: #missing,
+                    });
+                }
+            }
+        }
+    };
+    let check_already_set = ts_check_already_set(input, None);
+    let (error_ty, error_def) = match error {
+        Some(error) => (quote! { #error }, quote! {}),
+        None => (quote! { #build_error }, ts_build_error(input)),
+    };
+    let self_ty = build_fn_self(input);
+    let build_where_clause = build_fn_where_clause(input);
+    let (build_fn_name, build_fn_vis) = builder_attr_build_fn(input)
+        .unwrap_or_else(|_| (format_ident!("build"), builder_item_vis(input)));
+
+    if is_infallible_build(input) {
+        let doc = ts_doc(format!("Builds `{}`.", origin_name));
+        let must_use = ts_must_use_build(input);
+        return quote! {
+            #[automatically_derived]
+            impl #impl_generics #builder_name #ty_generics #where_clause {
+                #doc
+                #must_use
+                #build_fn_vis fn #build_fn_name(#self_ty) -> #built_ty #build_where_clause {
+                    #defaults
+                    #origin
+                }
+            }
+        };
+    }
+
+    let doc = ts_doc(format!(
+        "Builds `{}`, or returns an error if a required field is not set or a value is invalid.",
+        origin_name
+    ));
+
+    quote! {
+        #error_def
+
+        #[automatically_derived]
+        impl #impl_generics #builder_name #ty_generics #where_clause {
+            #doc
+            #build_fn_vis fn #build_fn_name(#self_ty) -> ::std::result::Result<#built_ty, #error_ty>
+            #build_where_clause
+            {
+                #check_missing
+                #check_already_set
+                #defaults
+                #check_valid
+                #build_sub_builders
+                ::std::result::Result::Ok(#origin)
+            }
+        }
+    }
+}
+
+/// Returns the values of the fields taken out of the builder by `build`
+/// and `try_build` after everything is checked, such as
+/// `{ self.__set[0] = false; self.executable.take() }.unwrap()`, which
+/// never fail. The one of `#[builder(default_try = ...)]` is the local
+/// of `ts_default_try_local` if it is not set, and the one of
+/// `#[builder(sub_builder)]` is the value built by its builder, whose
+/// slot is taken out as well if it is built in place.
+fn ts_build_values(input: &DeriveInput) -> Vec<(syn::Ident, TokenStream)> {
+    origin_fields(input)
+        .map(|field| {
+            let name = field.ident.clone().unwrap();
+            let value = ts_take_field(input, &field);
+            let value = if builder_attr_default_try(&field).is_some() {
+                let is_set = ts_is_set(input, &field, quote! { self });
+                let default = default_local_name(&field);
+                quote! {
+                    if #is_set {
+                        ::std::option::Option::unwrap_or_else(#value, || ::std::unreachable!())
+                    } else {
+                        ::std::option::Option::unwrap_or_else(#default, || ::std::unreachable!())
+                    }
+                }
+            } else if builder_attr_sub_builder(&field) {
+                let built = built_local_name(&field);
+                if builds_sub_builder_in_place(input, &field) {
+                    // clears the flags of the field.
+                    quote! {
+                        {
+                            ::std::mem::drop(#value);
+                            #built
+                        }
+                    }
+                } else {
+                    quote! { #built }
+                }
+            } else if !is_required_field(&field) {
+                // optional or multiple value field
+                ts_default_field(input, &field, value)
+            } else {
+                // required field, which is checked before.
+                quote! { ::std::option::Option::unwrap_or_else(#value, || ::std::unreachable!()) }
+            };
+            (name, value)
+        })
+        .collect()
+}
+
+/// This function returns `TokenStream` which represents a code such as
+/// ```ignore
+/// #[derive(Debug)]
+/// pub enum CommandBuilderError {
+///     UninitializedFields { names: Vec<&'static str> },
+///     ValidationError { field: Option<&'static str>, message: String },
+/// }
+///
+/// impl CommandBuilderError {
+///     pub fn fields(&self) -> &[&'static str] { ... }
+/// }
+///
+/// impl Display for CommandBuilderError { ... }
+///
+/// impl std::error::Error for CommandBuilderError {}
+/// ```
+///
+/// The error has the visibility of the builder, so that it is nameable
+/// wherever the builder is but is not exported by a `pub(crate)` struct.
+///
+/// With `#[builder(error_derive = "thiserror")]`, `Display` and
+/// `std::error::Error` are derived by `thiserror::Error` with the same
+/// messages instead, so that the crate using it must depend on
+/// `thiserror`.
+fn ts_build_error(input: &DeriveInput) -> TokenStream {
+    let build_error = build_error_name(input);
+    let vis = builder_item_vis(input);
+    let has_init_error = origin_fields(input).any(|field| {
+        builder_attr_default_try(&field).is_some() || builder_attr_sub_builder(&field)
+    });
+    let messages = quote! {
+        #[doc(hidden)]
+        fn uninitialized_message(names: &[&'static str]) -> ::std::string::String {
+            if names.len() == 1 {
+                return ::std::format!("field `{}` was not set", names[0]);
+            }
+            let mut quoted = ::std::vec::Vec::new();
+            for name in names {
+                quoted.push(::std::format!("`{}`", name));
+            }
+            ::std::format!("fields {} were not set", quoted.join(", "))
+        }
+
+        #[doc(hidden)]
+        fn validation_message(
+            field: ::std::option::Option<&'static str>,
+            message: &str,
+        ) -> ::std::string::String {
+            match field {
+                ::std::option::Option::Some(field) => {
+                    ::std::format!("field `{}` is invalid: {}", field, message)
+                }
+                ::std::option::Option::None => ::std::format!("invalid value: {}", message),
+            }
+        }
+    };
+    let (derive, uninitialized_attr, validation_attr, init_attr, error_impls) =
+        if builder_attr_error_derive_thiserror(input) {
+            (
+                quote! { #[derive(Debug, thiserror::Error)] },
+                quote! { #[error("{}", #build_error::uninitialized_message(.names))] },
+                quote! { #[error("{}", #build_error::validation_message(*.field, .message))] },
+                quote! { #[error("field `{}` failed to be initialized: {}", .field, .source)] },
+                quote! {},
+            )
+        } else {
+            let (display_init, source) = if has_init_error {
+                (
+                    quote! {
+                        #build_error::InitError { field, source } => {
+                            ::std::write!(f, "field `{}` failed to be initialized: {}", field, source)
+                        }
+                    },
+                    quote! {
+                        fn source(&self) -> ::std::option::Option<&(dyn ::std::error::Error + 'static)> {
+                            match self {
+                                #build_error::InitError { source, .. } => {
+                                    ::std::option::Option::Some(&**source)
+                                }
+                                _ => ::std::option::Option::None,
+                            }
+                        }
+                    },
+                )
+            } else {
+                (quote! {}, quote! {})
+            };
+            (
+                quote! { #[derive(Debug)] },
+                quote! {},
+                quote! {},
+                quote! {},
+                quote! {
+                    #[automatically_derived]
+                    impl ::std::fmt::Display for #build_error {
+                        fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                            match self {
+                                #build_error::UninitializedFields { names } => {
+                                    f.write_str(&#build_error::uninitialized_message(names))
+                                }
+                                #build_error::ValidationError { field, message } => {
+                                    f.write_str(&#build_error::validation_message(*field, message))
+                                }
+                                #display_init
+                            }
+                        }
+                    }
+
+                    #[automatically_derived]
+                    impl ::std::error::Error for #build_error {
+                        #source
+                    }
+                },
+            )
+        };
+    let (init_variant, init_fields) = if has_init_error {
+        (
+            quote! {
+                /// The error of `#[builder(default_try = ...)]` or
+                /// `#[builder(sub_builder)]` of the field.
+                #init_attr
+                InitError {
+                    /// The name of the field.
+                    field: &'static str,
+                    /// The error returned by the default.
+                    source: ::std::boxed::Box<
+                        dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync,
+                    >,
+                },
+            },
+            quote! {
+                #build_error::InitError { field, .. } => ::std::slice::from_ref(field),
+            },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
+
+    let doc = ts_doc(format!(
+        "The error returned when `{}` fails to be built.",
+        origin_name(input)
+    ));
+
+    quote! {
+        #doc
+        #derive
+        #vis enum #build_error {
+            /// The required fields which are not set.
+            #uninitialized_attr
+            UninitializedFields {
+                /// The names of the fields.
+                names: ::std::vec::Vec<&'static str>,
+            },
+            /// The value rejected by `#[builder(validate = "...")]`, or the
+            /// field set twice with `#[builder(once = "error")]`.
+            #validation_attr
+            ValidationError {
+                /// The name of the field, if the value is of a field.
+                field: ::std::option::Option<&'static str>,
+                /// The message returned by the validation.
+                message: ::std::string::String,
+            },
+            #init_variant
+        }
+
+        #[automatically_derived]
+        impl #build_error {
+            /// Returns the names of the fields which caused the error.
+            #vis fn fields(&self) -> &[&'static str] {
+                match self {
+                    #build_error::UninitializedFields { names } => names,
+                    #build_error::ValidationError {
+                        field: ::std::option::Option::Some(field),
+                        ..
+                    } => ::std::slice::from_ref(field),
+                    #build_error::ValidationError { .. } => &[],
+                    #init_fields
+                }
+            }
+
+            #messages
+        }
+
+        #error_impls
+    }
+}
+
+/// This function returns `TokenStream` which represents an expression
+/// constructing the original struct from the values of its fields,
+/// such as
+/// ```ignore
+/// Command {
+///     executable: self.executable.take().ok_or_else(|| CommandBuilderError::UninitializedFields { names: vec!["executable"] })?,
+///     current_dir: self.current_dir.take(),
+/// }
+/// ```
+///
+/// or `Command::new_unchecked(...)` with the values in the order of
+/// declaration if `#[builder(build_with = "Command::new_unchecked")]`
+/// is given.
+///
+/// The struct literal works for a `#[non_exhaustive]` struct as well,
+/// because the builder is always in the crate defining the struct and
+/// every field of the struct is given to the derive.
+///
+/// With `#[builder(wrap = "Arc")]`, the struct is wrapped such as
+/// `Arc::new(Command { ... })`.
+fn ts_construct_origin(input: &DeriveInput, values: Vec<(syn::Ident, TokenStream)>) -> TokenStream {
+    // `values` are in the same order as the fields.
+    let cfgs: Vec<Vec<syn::Attribute>> = origin_fields(input)
+        .map(|field| field_cfg_attrs(&field).into_iter().cloned().collect())
+        .collect();
+    let origin = match builder_attr_build_with(input) {
+        Some(Ok(ctor)) => {
+            let values = values
+                .into_iter()
+                .zip(cfgs)
+                .map(|((_, value), cfgs)| quote! { #(#cfgs)* #value });
+            quote! { #ctor(#(#values),*) }
+        }
+        _ => {
+            let origin_name = match enums::origin_variant(input) {
+                Some((enum_name, variant)) => quote! { #enum_name::#variant },
+                None => {
+                    let origin_name = origin_name(input);
+                    quote! { #origin_name }
+                }
+            };
+            let fields = values
+                .into_iter()
+                .zip(cfgs)
+                .map(|((name, value), cfgs)| quote! { #(#cfgs)* #name: #value, });
+            quote! {
+                #origin_name {
+                    #(#fields)*
+                }
+            }
+        }
+    };
+    match builder_attr_wrap(input) {
+        Some(Ok(wrap)) => quote! { #wrap::new(#origin) },
+        _ => origin,
+    }
+}
+
+/// Returns the type which the builder builds, which is the original
+/// struct such as `Command<T>`, or the one wrapped by
+/// `#[builder(wrap = "...")]` such as `Arc<Command<T>>`.
+fn ts_built_type(input: &DeriveInput) -> TokenStream {
+    let origin_name = origin_name(input);
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    match builder_attr_wrap(input) {
+        Some(Ok(wrap)) => quote! { #wrap<#origin_name #ty_generics> },
+        _ => quote! { #origin_name #ty_generics },
+    }
+}
+
+/// Returns the expression which takes the value of the field out of
+/// the builder, such as `{ self.__set[0] = false; self.executable.take() }`,
+/// or moves it with the owned pattern. A collection is replaced with an
+/// empty one, and the flags of the field are cleared so that the builder
+/// is left as the field is not set. With `#[builder(clone_build)]` on
+/// the struct or `#[builder(keep)]` on the field it clones the value
+/// instead, so that only then the field must implement `Clone`.
+fn ts_take_field(input: &DeriveInput, field: &syn::Field) -> TokenStream {
+    let name = field.ident.as_ref().unwrap();
+    if builder_attr_skip(field) {
+        // replaced by the default in `ts_default_field`.
+        return TokenStream::new();
+    }
+    if builder_attr_clone_build(input) || builder_attr_keep(field) {
+        // reports the field which does not implement `Clone`.
+        let clone = quote_spanned!(field.ty.span()=> ::std::clone::Clone::clone);
+        return quote! { #clone(&self.#name) };
+    }
+    if builder_pattern(input) == BuilderPattern::Owned {
+        return quote! { self.#name };
+    }
+    let set_flags = set_flags_name();
+    let index = set_flag_index(input, field);
+    let clear_once = match once_set_index(input, field) {
+        Some(once_index) => {
+            let once_set = once_set_name();
+            quote! { self.#once_set[#once_index] = false; }
+        }
+        None => TokenStream::new(),
+    };
+    let take = if is_collection_field(field) {
+        let new = ts_new_collection(&field_type(field));
+        quote! { ::std::mem::replace(&mut self.#name, #new) }
+    } else {
+        quote! { self.#name.take() }
+    };
+    quote! {
+        {
+            self.#set_flags[#index] = false;
+            #clear_once
+            #take
+        }
+    }
+}
+
+/// Returns the expression of the error for the required field which
+/// is not set, such as
+/// `CommandBuilderError::UninitializedFields { names: vec!["executable"] }` or
+/// `ConfigError::missing_field("executable")`.
+fn ts_missing_field_error(input: &DeriveInput, field: &syn::Field) -> TokenStream {
+    let name_str = field_display_name(field);
+    match builder_attr_error(input) {
+        // Point at the attribute if `missing_field` is not found.
+        Some(Ok(error)) => {
+            quote_spanned! {error.segments.last().unwrap().ident.span()=>
+                #error::missing_field(#name_str)
+            }
+        }
+        _ => {
+            let build_error = build_error_name(input);
+            quote! {
+                #build_error::UninitializedFields {
+                    names: ::std::vec![#name_str],
+                }
+            }
+        }
+    }
+}
+
+/// Returns the expression of the error for the field whose
+/// `#[builder(default_try = ...)]` or sub-builder of
+/// `#[builder(sub_builder)]` returns `Err`, boxed as `source`,
+/// such as `CommandBuilderError::InitError { field: "executable", source }`
+/// or `ConfigError::init_field("executable", source)`.
+fn ts_init_field_error(input: &DeriveInput, field: &syn::Field) -> TokenStream {
+    let name_str = field_display_name(field);
+    match builder_attr_error(input) {
+        // Point at the attribute if `init_field` is not found.
+        Some(Ok(error)) => {
+            quote_spanned! {error.segments.last().unwrap().ident.span()=>
+                #error::init_field(#name_str, source)
+            }
+        }
+        _ => {
+            let build_error = build_error_name(input);
+            quote! {
+                #build_error::InitError {
+                    field: #name_str,
+                    source,
+                }
+            }
+        }
+    }
+}
+
+/// Returns the expression of the error for the field whose
+/// `#[builder(validate = "...")]` function returns `Err(message)`,
+/// such as `CommandBuilderError::ValidationError { field: Some("executable"), message }`
+/// or `ConfigError::invalid_field("executable", message)`.
+fn ts_invalid_field_error(input: &DeriveInput, field: &syn::Field) -> TokenStream {
+    let name_str = field_display_name(field);
+    ts_invalid_error(input, quote! { #name_str })
+}
+
+/// Returns the statement which checks the value which the field is
+/// built with by its `#[builder(validate = "...")]` function before any
+/// value is taken out of the builder, such as
+/// ```ignore
+/// if self.__set[0] {
+///     if let Err(message) = check_executable(self.executable.as_ref().unwrap()) {
+///         return Err(CommandBuilderError::ValidationError { field: Some("executable"), message });
+///     }
+/// }
+/// ```
+///
+/// whose value is borrowed from the builder if the field is set, or
+/// else from the default evaluated by `ts_builder_defaults` or
+/// `ts_default_try_local`. The required field which is not set is
+/// reported as missing instead. `report` turns the error into the
+/// statement reporting it. The field with `#[builder(sub_builder)]` is
+/// checked after it is built instead.
+fn ts_check_valid(
+    input: &DeriveInput,
+    field: &syn::Field,
+    report: impl Fn(TokenStream) -> TokenStream,
+) -> TokenStream {
+    let validate = match builder_attr_validate(field) {
+        Some(Ok(validate)) => validate,
+        _ => return TokenStream::new(),
+    };
+    let name = field.ident.as_ref().unwrap();
+    let cfgs = field_cfg_attrs(field);
+    let value = syn::Ident::new("value", proc_macro2::Span::mixed_site());
+    let report = report(ts_invalid_field_error(input, field));
+    let check = |value: TokenStream| {
+        quote! {
+            if let ::std::result::Result::Err(message) = #validate(#value) {
+                #report
+            }
+        }
+    };
+    // the builder holds the same type as the field.
+    let holds_field = optional_type_of(field).is_some() || is_collection_field(field);
+    let has_default = builder_attr_default(field).is_some() || builder_attr_default_try(field).is_some();
+    if holds_field && !has_default {
+        // the value in the builder is built as it is whether it is set or not.
+        let check = check(quote! { &self.#name });
+        return quote! {
+            #(#cfgs)*
+            #check
+        };
+    }
+    let is_set = ts_is_set(input, field, quote! { self });
+    let check_set = if holds_field {
+        check(quote! { &self.#name })
+    } else {
+        // not inferred by the argument, which may be coerced by `Deref`.
+        check(quote! {
+            match self.#name {
+                ::std::option::Option::Some(ref #value) => #value,
+                ::std::option::Option::None => ::std::unreachable!(),
+            }
+        })
+    };
+    let check_unset = if has_early_default(field) && optional_type_of(field).is_some() {
+        let default = default_local_name(field);
+        check(quote! { &#default })
+    } else if has_early_default(field) || builder_attr_default_try(field).is_some() {
+        // `None` after the error of `default_try` is pushed.
+        let default = default_local_name(field);
+        let check = check(quote! { #value });
+        quote! {
+            if let ::std::option::Option::Some(#value) = ::std::option::Option::as_ref(&#default) {
+                #check
+            }
+        }
+    } else if builder_attr_keep_option(field) && builder_attr_default(field).is_none() {
+        check(quote! { &::std::option::Option::None })
+    } else {
+        // the required field, or the wrong default reported by `validate_fields`.
+        TokenStream::new()
+    };
+    quote! {
+        #(#cfgs)*
+        if #is_set {
+            #check_set
+        } else {
+            #check_unset
+        }
+    }
+}
+
+/// Returns the expression of the error for the field whose name is
+/// `name`, an expression of `&'static str`, with `message`.
+fn ts_invalid_error(input: &DeriveInput, name: TokenStream) -> TokenStream {
+    match builder_attr_error(input) {
+        // Point at the attribute if `invalid_field` is not found.
+        Some(Ok(error)) => {
+            quote_spanned! {error.segments.last().unwrap().ident.span()=>
+                #error::invalid_field(#name, message)
+            }
+        }
+        _ => {
+            let build_error = build_error_name(input);
+            quote! {
+                #build_error::ValidationError {
+                    field: ::std::option::Option::Some(#name),
+                    message,
+                }
+            }
+        }
+    }
+}
+
+/// Returns the statement which returns the error for the first field
+/// with `#[builder(once = "error")]` set twice, such as
+/// ```ignore
+/// if let Some(field) = self.__already_set {
+///     let message = String::from("it was already set");
+///     return Err(CommandBuilderError::ValidationError { field: Some(field), message });
+/// }
+/// ```
+///
+/// or the one pushing it to `errors` of `try_build`.
+fn ts_check_already_set(input: &DeriveInput, errors: Option<&syn::Ident>) -> TokenStream {
+    if !has_once_error(input) {
+        return TokenStream::new();
+    }
+    let already_set = already_set_name();
+    let invalid = ts_invalid_error(input, quote! { field });
+    let report = match errors {
+        Some(errors) => quote! { #errors.push(#invalid); },
+        None => quote! { return ::std::result::Result::Err(#invalid); },
+    };
+    quote! {
+        if let ::std::option::Option::Some(field) = self.#already_set {
+            let message = ::std::string::String::from("it was already set");
+            #report
+        }
+    }
+}
+
+/// This function produce TokenStream which represents
+/// some source code such as
+/// ```ignore
+/// impl CommandBuilder {
+///     pub fn try_build(&mut self) -> Result<Command, Vec<CommandBuilderError>> {
+///         let mut errors = Vec::new();
+///         if !self.__set[0] {
+///             errors.push(CommandBuilderError::UninitializedFields { names: vec!["executable"] });
+///         }
+///         if self.__set[0] {
+///             if let Err(message) = check_executable(self.executable.as_ref().unwrap()) {
+///                 errors.push(CommandBuilderError::ValidationError { field: Some("executable"), message });
+///             }
+///         }
+///         if !errors.is_empty() {
+///             return Err(errors);
+///         }
+///         Ok(Command {
+///             executable: { self.__set[0] = false; self.executable.take() }.unwrap(),
+///             current_dir: { self.__set[1] = false; self.current_dir.take() },
+///         })
+///     }
+/// }
+/// ```
+///
+/// Unlike `build`, it does not stop at the first problem but reports
+/// every required field which is not set and every field rejected by
+/// its `#[builder(validate = "...")]` function, in the order of the
+/// fields, followed by the errors of the sub-builders, which are built
+/// after the others are checked. As `build` does, it checks everything
+/// before any value is taken out, so that the builder is left as it is
+/// if some error is found, and puts back the sub-builders built in place.
+/// It is not generated if `build` never fails.
+fn ts_builder_impl_try_build_fn(input: &DeriveInput) -> TokenStream {
+    if typestate::builder_attr_typestate(input) || is_infallible_build(input) {
+        return TokenStream::new();
+    }
+    let origin_name = origin_name(input);
+    let builder_name = builder_name(input);
+    let vis = builder_item_vis(input);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let error_ty = match builder_attr_error(input) {
+        Some(Ok(error)) => quote! { #error },
+        _ => {
+            let build_error = build_error_name(input);
+            quote! { #build_error }
+        }
+    };
+    // not to collide with the names of the fields.
+    let errors = syn::Ident::new("errors", proc_macro2::Span::mixed_site());
+    let value = syn::Ident::new("value", proc_macro2::Span::mixed_site());
+
+    let report = |error: TokenStream| quote! { #errors.push(#error); };
+    // in the order of the fields, which is the order of the errors.
+    let check_valid: TokenStream = origin_fields(input)
+        .map(|field| {
+            let check_missing = if is_required_field(&field) {
+                let cfgs = field_cfg_attrs(&field);
+                let is_set = ts_is_set(input, &field, quote! { self });
+                let missing = ts_missing_field_error(input, &field);
+                quote! {
+                    #(#cfgs)*
+                    if !#is_set {
+                        #errors.push(#missing);
+                    }
+                }
+            } else {
+                quote! {}
+            };
+            if builder_attr_sub_builder(&field) {
+                // checked after it is built.
+                return check_missing;
+            }
+            let default_try = ts_default_try_local(input, &field, Some(&errors));
+            let check_valid = ts_check_valid(input, &field, report);
+            quote! {
+                #check_missing
+                #default_try
+                #check_valid
+            }
+        })
+        .collect();
+    // built after the other fields are checked, and put back if any
+    // error is found.
+    let sub_builders: Vec<syn::Field> = origin_fields(input)
+        .filter(builder_attr_sub_builder)
+        .collect();
+    let build_sub_builders: TokenStream = sub_builders
+        .iter()
+        .map(|field| {
+            let built = built_local_name(field);
+            let cfgs = field_cfg_attrs(field);
+            let build = ts_build_sub_builder_field(input, field);
+            let init = ts_init_field_error(input, field);
+            let on_missing = if is_required_field(field) {
+                // reported before.
+                quote! { ::std::option::Option::None }
+            } else {
+                let missing = ts_missing_field_error(input, field);
+                quote! {
+                    {
+                        #errors.push(#missing);
+                        ::std::option::Option::None
+                    }
+                }
+            };
+            let check_valid = match builder_attr_validate(field) {
+                Some(Ok(validate)) => {
+                    let invalid = ts_invalid_field_error(input, field);
+                    quote! {
+                        #(#cfgs)*
+                        if let ::std::option::Option::Some(ref #value) = #built {
+                            if let ::std::result::Result::Err(message) = #validate(#value) {
+                                #errors.push(#invalid);
+                            }
+                        }
+                    }
+                }
+                _ => TokenStream::new(),
+            };
+            quote! {
+                #(#cfgs)*
+                let #built = match #build {
+                    ::std::option::Option::Some(::std::result::Result::Ok(value)) => {
+                        ::std::option::Option::Some(value)
+                    }
+                    ::std::option::Option::Some(::std::result::Result::Err(source)) => {
+                        #errors.push(#init);
+                        ::std::option::Option::None
+                    }
+                    ::std::option::Option::None => #on_missing,
+                };
+                #check_valid
+            }
+        })
+        .collect();
+    let restore_sub_builders: TokenStream = sub_builders
+        .iter()
+        .filter(|field| builds_sub_builder_in_place(input, field))
+        .map(|field| {
+            let built = built_local_name(field);
+            let cfgs = field_cfg_attrs(field);
+            let restore = ts_restore_sub_builder(input, field);
+            quote! {
+                #(#cfgs)*
+                if let ::std::option::Option::Some(#built) = #built {
+                    #restore
+                }
+            }
+        })
+        .collect();
+    let unwrap_sub_builders: TokenStream = sub_builders
+        .iter()
+        .map(|field| {
+            let built = built_local_name(field);
+            let cfgs = field_cfg_attrs(field);
+            quote! {
+                #(#cfgs)*
+                let #built = ::std::option::Option::unwrap_or_else(#built, || ::std::unreachable!());
+            }
+        })
+        .collect();
+    let values = ts_build_values(input);
+    let origin = ts_construct_origin(input, values);
+    let defaults = ts_builder_defaults(input);
+    let check_already_set = ts_check_already_set(input, Some(&errors));
+    let built_ty = ts_built_type(input);
+    let self_ty = build_fn_self(input);
+    let build_where_clause = build_fn_where_clause(input);
+
+    let doc = ts_doc(format!(
+        "Builds `{}`, or returns the errors of all the fields at once.",
+        origin_name
+    ));
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #builder_name #ty_generics #where_clause {
+            #doc
+            #vis fn try_build(#self_ty) -> ::std::result::Result<#built_ty, ::std::vec::Vec<#error_ty>>
+            #build_where_clause
+            {
+                #defaults
+                let mut #errors = ::std::vec::Vec::new();
+                #check_already_set
+                #check_valid
+                #build_sub_builders
+                if !#errors.is_empty() {
+                    #restore_sub_builders
+                    return ::std::result::Result::Err(#errors);
+                }
+                #unwrap_sub_builders
+                ::std::result::Result::Ok(#origin)
+            }
+        }
+    }
+}
+
+/// This function produce TokenStream which represents
+/// some source code such as
+/// ```ignore
+/// impl CommandBuilder {
+///     pub fn build_or_panic(&mut self) -> Command {
+///         self.build().expect("failed to build `Command`")
+///     }
+/// }
+/// ```
+///
+/// only if `#[builder(panic_helper)]` is given.
+/// The error type must implement `Debug`.
+fn ts_builder_impl_build_or_panic_fn(input: &DeriveInput) -> TokenStream {
+    if !builder_attr_panic_helper(input) {
+        return TokenStream::new();
+    }
+    let origin_name = origin_name(input);
+    let builder_name = builder_name(input);
+    let vis = builder_item_vis(input);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let self_ty = build_fn_self(input);
+    let build_where_clause = build_fn_where_clause(input);
+    let (build_fn_name, _) = builder_attr_build_fn(input)
+        .unwrap_or_else(|_| (format_ident!("build"), builder_item_vis(input)));
+    let built_ty = ts_built_type(input);
+    let message = format!("failed to build `{}`", origin_name);
+    let build = if is_infallible_build(input) {
+        quote! { self.#build_fn_name() }
+    } else {
+        quote! { self.#build_fn_name().expect(#message) }
+    };
+
+    let doc = ts_doc(format!("Builds `{}`, or panics if it fails.", origin_name));
+    let must_use = ts_must_use_build(input);
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #builder_name #ty_generics #where_clause {
+            #doc
+            #must_use
+            #vis fn build_or_panic(#self_ty) -> #built_ty #build_where_clause {
+                #build
+            }
+        }
+    }
+}
+
+/// This function returns `TokenStream` which represents
+/// a code such as
+/// ```ignore
+/// impl CommandBuilder {
+///     pub fn build_arc(&mut self) -> Result<Arc<Command>, CommandBuilderError> {
+///         self.build().map(Arc::new)
+///     }
+///
+///     pub fn build_boxed(&mut self) -> Result<Box<Command>, CommandBuilderError> {
+///         self.build().map(Box::new)
+///     }
+/// }
+/// ```
+///
+/// only if `#[builder(build_wrappers)]` is given. They are named after
+/// `build`, such as `finish_arc` with `#[builder(build_fn = "finish")]`.
+/// The struct is moved into the allocation after it is built, since
+/// there is no way to construct it in place. With `#[builder(typestate)]`
+/// they are only on the builder whose required fields are all set.
+fn ts_builder_impl_build_wrappers_fn(input: &DeriveInput) -> TokenStream {
+    if !builder_attr_build_wrappers(input) {
+        return TokenStream::new();
+    }
+    let origin_name = origin_name(input);
+    let builder_name = builder_name(input);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let builder_ty_args: Vec<TokenStream> = origin_generic_args(input)
+        .into_iter()
+        .chain(typestate::complete_state_args(input))
+        .collect();
+    let builder_ty = if builder_ty_args.is_empty() {
+        quote! { #builder_name }
+    } else {
+        quote! { #builder_name<#(#builder_ty_args),*> }
+    };
+    let self_ty = build_fn_self(input);
+    let build_where_clause = build_fn_where_clause(input);
+    let (build_fn_name, build_fn_vis) = builder_attr_build_fn(input)
+        .unwrap_or_else(|_| (format_ident!("build"), builder_item_vis(input)));
+    let is_infallible = typestate::builder_attr_typestate(input) || is_infallible_build(input);
+    let error_ty = match builder_attr_error(input) {
+        Some(Ok(error)) => quote! { #error },
+        _ => {
+            let build_error = build_error_name(input);
+            quote! { #build_error }
+        }
+    };
+    let must_use = ts_must_use_build(input);
+
+    let wrappers = build_wrapper_fn_names(input).into_iter().map(|(fn_name, pointer)| {
+        let wrapper = match pointer {
+            "Arc" => quote! { ::std::sync::Arc },
+            _ => quote! { ::std::boxed::Box },
+        };
+        let built_ty = quote! { #wrapper<#origin_name #ty_generics> };
+        if is_infallible {
+            let doc = ts_doc(format!("Builds `{}` into `{}`.", origin_name, pointer));
+            quote! {
+                #doc
+                #must_use
+                #build_fn_vis fn #fn_name(#self_ty) -> #built_ty #build_where_clause {
+                    #wrapper::new(self.#build_fn_name())
+                }
+            }
+        } else {
+            let doc = ts_doc(format!(
+                "Builds `{}` as `{}` does, and moves it into `{}`.",
+                origin_name, build_fn_name, pointer
+            ));
+            quote! {
+                #doc
+                #build_fn_vis fn #fn_name(#self_ty) -> ::std::result::Result<#built_ty, #error_ty>
+                #build_where_clause
+                {
+                    ::std::result::Result::map(self.#build_fn_name(), #wrapper::new)
+                }
+            }
+        }
+    });
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #builder_ty #where_clause {
+            #(#wrappers)*
+        }
+    }
+}
+
+/// This function produce TokenStream which represents
+/// some source code such as
+/// ```ignore
+/// impl CommandBuilder {
+///     pub fn missing_fields(&self) -> Vec<&'static str> {
+///         let mut missing = Vec::new();
+///         if !self.__set[0] {
+///             missing.push("executable");
+///         }
+///         missing
+///     }
+///
+///     pub fn is_complete(&self) -> bool {
+///         self.missing_fields().is_empty()
+///     }
+/// }
+/// ```
+///
+/// which tell the required fields which are not set, that is,
+/// whether `build` does not fail by a missing field. Either method is
+/// not generated if the setter of a field has the same name.
+fn ts_builder_impl_missing_fields_fn(input: &DeriveInput) -> TokenStream {
+    let builder_name = builder_name(input);
+    let vis = builder_item_vis(input);
+    let generics = builder_generics(input);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let missing = syn::Ident::new("missing", proc_macro2::Span::mixed_site());
+    let collect_missing = ts_collect_missing(input, &missing);
+    let has_missing_fields = !is_field_method_name(input, "missing_fields");
+    let missing_fields = if has_missing_fields {
+        quote! {
+            /// Returns the names of the required fields which are not set.
+            #vis fn missing_fields(&self) -> ::std::vec::Vec<&'static str> {
+                #collect_missing
+                #missing
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+    let is_complete = match is_field_method_name(input, "is_complete") {
+        true => TokenStream::new(),
+        false if has_missing_fields => quote! {
+            /// Returns whether all the required fields are set.
+            #vis fn is_complete(&self) -> bool {
+                self.missing_fields().is_empty()
+            }
+        },
+        false => quote! {
+            /// Returns whether all the required fields are set.
+            #vis fn is_complete(&self) -> bool {
+                #collect_missing
+                #missing.is_empty()
+            }
+        },
+    };
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #builder_name #ty_generics #where_clause {
+            #missing_fields
+            #is_complete
+        }
+    }
+}
+
+/// This function returns `TokenStream` which represents
+/// a code such as
+/// ```ignore
+/// let mut missing = Vec::new();
+/// if !self.__set[0] {
+///     missing.push("executable");
+/// }
+/// ```
+///
+/// which collects the names of the required fields which are not set
+/// into `missing`, for `missing_fields` and the check in `build`.
+fn ts_collect_missing(input: &DeriveInput, missing: &syn::Ident) -> TokenStream {
+    let checks = origin_fields(input).filter(is_required_field).map(|field| {
+        let is_set = ts_is_set(input, &field, quote! { self });
+        let name_str = field_display_name(&field);
+        let cfgs = field_cfg_attrs(&field);
+        quote! {
+            #(#cfgs)*
+            if !#is_set {
+                #missing.push(#name_str);
+            }
+        }
+    });
+
+    quote! {
+        let mut #missing = ::std::vec::Vec::new();
+        #(#checks)*
+    }
+}
+
+/// This function returns `TokenStream` which represents
+/// a code such as
+/// ```ignore
+/// impl TryFrom<CommandBuilder> for Command {
+///     type Error = CommandBuilderError;
+///
+///     fn try_from(mut builder: CommandBuilder) -> Result<Command, CommandBuilderError> {
+///         builder.build()
+///     }
+/// }
+/// ```
+///
+/// only if `#[builder(try_from)]` is given, so that the builder works
+/// with the code generic over `TryFrom`. If `build` never fails, `From`
+/// is implemented instead and `TryFrom` comes from it, which is for
+/// the builder whose required fields are all set with
+/// `#[builder(typestate)]`. The type parameters are bounded by `Clone`
+/// with `#[builder(clone_build)]` as `build` is.
+fn ts_origin_impl_try_from(input: &DeriveInput) -> TokenStream {
+    if !builder_attr_try_from(input) {
+        return TokenStream::new();
+    }
+    let builder_name = builder_name(input);
+    let mut generics = input.generics.clone();
+    if builder_attr_clone_build(input) {
+        // the same bounds as `build_fn_where_clause`.
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(clone_bounds(input));
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let builder_ty_args: Vec<TokenStream> = origin_generic_args(input)
+        .into_iter()
+        .chain(typestate::complete_state_args(input))
+        .collect();
+    let built_ty = ts_built_type(input);
+    let builder_ty = if builder_ty_args.is_empty() {
+        quote! { #builder_name }
+    } else {
+        quote! { #builder_name<#(#builder_ty_args),*> }
+    };
+    let (build_fn_name, _) = builder_attr_build_fn(input)
+        .unwrap_or_else(|_| (format_ident!("build"), builder_item_vis(input)));
+    let builder = syn::Ident::new("builder", proc_macro2::Span::mixed_site());
+    let builder_arg = match builder_pattern(input) {
+        BuilderPattern::Mutable if !builder_attr_clone_build(input) => quote! { mut #builder },
+        _ => quote! { #builder },
+    };
+
+    if typestate::builder_attr_typestate(input) || is_infallible_build(input) {
+        return quote! {
+            #[automatically_derived]
+            impl #impl_generics ::std::convert::From<#builder_ty> for #built_ty #where_clause {
+                fn from(#builder_arg: #builder_ty) -> #built_ty {
+                    #builder.#build_fn_name()
+                }
+            }
+        };
+    }
+
+    let error_ty = match builder_attr_error(input) {
+        Some(Ok(error)) => quote! { #error },
+        _ => {
+            let build_error = build_error_name(input);
+            quote! { #build_error }
+        }
+    };
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::std::convert::TryFrom<#builder_ty> for #built_ty #where_clause {
+            type Error = #error_ty;
+
+            fn try_from(#builder_arg: #builder_ty) -> ::std::result::Result<#built_ty, #error_ty> {
+                #builder.#build_fn_name()
+            }
+        }
+    }
+}
+
+/// This function returns `TokenStream` which represents
+/// a code such as
+/// ```ignore
+/// impl From<Command> for CommandBuilder {
+///     fn from(origin: Command) -> CommandBuilder {
+///         CommandBuilder {
+///             executable: Some(origin.executable),
+///             args: origin.args,
+///             current_dir: origin.current_dir,
+///             __set: [true, true, true],
+///         }
+///     }
+/// }
+///
+/// impl Command {
+///     pub fn to_builder(&self) -> CommandBuilder {
+///         CommandBuilder {
+///             executable: Some(Clone::clone(&self.executable)),
+///             args: Clone::clone(&self.args),
+///             current_dir: Clone::clone(&self.current_dir),
+///             __set: [true, true, true],
+///         }
+///     }
+/// }
+/// ```
+///
+/// so that an existing value is able to be changed and built again.
+/// The builder has every field set, which is `CommandBuilder<true>`
+/// with `#[builder(typestate)]`, while the setter of the field with
+/// `#[builder(once)]` is still able to be called once, since the value
+/// does not come from the setter. See `once_set_name`.
+///
+/// `From` moves the fields out of the struct and requires nothing of
+/// them, so that it is always generated. `to_builder` is generated
+/// only if `#[builder(from_self)]` is given, because it requires `Clone`
+/// on the fields in the builder, and the type parameters are bounded
+/// by `Clone` as `derive(Clone)` does. Neither is generated for the
+/// builder of a variant, whose fields are not able to be moved out of
+/// the enum.
+///
+/// The fields with `#[builder(skip)]` are dropped, and the ones with
+/// `#[builder(sub_builder)]` are turned into their builders by `From`
+/// of their own derive.
+fn ts_builder_impl_from_origin(input: &DeriveInput) -> TokenStream {
+    if enums::origin_variant(input).is_some() {
+        return TokenStream::new();
+    }
+    let origin_name = origin_name(input);
+    let builder_name = builder_name(input);
+    let vis = builder_item_vis(input);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let builder_ty_args: Vec<TokenStream> = origin_generic_args(input)
+        .into_iter()
+        .chain(typestate::complete_state_args(input))
+        .collect();
+    let builder_ty = if builder_ty_args.is_empty() {
+        quote! { #builder_name }
+    } else {
+        quote! { #builder_name<#(#builder_ty_args),*> }
+    };
+    let origin = syn::Ident::new("origin", proc_macro2::Span::mixed_site());
+    let builder_fields = |value: &dyn Fn(&syn::Ident) -> TokenStream| -> TokenStream {
+        builder_fields(input)
+            .map(|field| {
+                let name = field.ident.as_ref().unwrap();
+                let cfgs = field_cfg_attrs(&field);
+                let value = value(name);
+                // the sub-builder is made by `From` of its own derive.
+                let value = if builder_attr_sub_builder(&field) {
+                    quote! { ::std::convert::From::from(#value) }
+                } else {
+                    value
+                };
+                // the builder holds the same type as the field.
+                if optional_type_of(&field).is_some() || is_collection_field(&field) {
+                    quote! { #(#cfgs)* #name: #value, }
+                } else {
+                    quote! { #(#cfgs)* #name: ::std::option::Option::Some(#value), }
+                }
+            })
+            .collect()
+    };
+    let moved_fields = builder_fields(&|name| quote! { #origin.#name });
+    let cloned_fields = builder_fields(&|name| quote! { ::std::clone::Clone::clone(&self.#name) });
+    let set_flags = ts_set_flags_init(input, |_| true);
+    let once_set = ts_once_set_init(input);
+    let already_set = ts_already_set_init(input);
+    let clone_bounds = clone_bounds(input);
+    let clone_where_clause = if clone_bounds.is_empty() {
+        quote! {}
+    } else {
+        quote! { where #(#clone_bounds),* }
+    };
+    let doc = ts_doc(format!(
+        "Creates a builder whose fields are set to the ones of this `{}`.",
+        origin_name
+    ));
+    let must_use = ts_must_use_builder(input);
+    let to_builder = if builder_attr_from_self(input) {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #origin_name #ty_generics #where_clause {
+                #doc
+                #must_use
+                #vis fn to_builder(&self) -> #builder_ty #clone_where_clause {
+                    #builder_name {
+                        #cloned_fields
+                        #set_flags
+                        #once_set
+                        #already_set
+                    }
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::std::convert::From<#origin_name #ty_generics> for #builder_ty #where_clause {
+            fn from(#origin: #origin_name #ty_generics) -> #builder_ty {
+                #builder_name {
+                    #moved_fields
+                    #set_flags
+                    #once_set
+                    #already_set
+                }
+            }
         }
+
+        #to_builder
     }
 }
 
-/// This function produce TokenStream which represents
-/// some source code such as
+/// This function returns `TokenStream` which represents
+/// a code such as
 /// ```ignore
-/// #[derive(Debug)]
-/// pub struct BuildError();
+/// impl CommandBuilder {
+///     pub fn reset(&mut self) -> &mut Self {
+///         *self = CommandBuilder::new();
+///         self
+///     }
+/// }
+/// ```
+///
+/// which returns the builder to the state `new` produces, since `build`
+/// leaves the builder half taken out. With the owned pattern it takes
+/// `self` and returns the new builder, which is `CommandBuilder<false>`
+/// with `#[builder(typestate)]`.
+///
+/// The fields with `#[builder(ctor_args)]` keep their values, so that
+/// the builder is made by a struct expression instead of `new`. It is
+/// not generated if the setter of a field is named `reset`.
+fn ts_builder_impl_reset_fn(input: &DeriveInput) -> TokenStream {
+    if is_field_method_name(input, "reset") {
+        return TokenStream::new();
+    }
+    let builder_name = builder_name(input);
+    let vis = builder_item_vis(input);
+    let generics = builder_generics(input);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let initial_ty_generics = initial_builder_ty_generics(input);
+    let doc = ts_doc("Resets all the fields as `new` does.".to_string());
+    let must_use = ts_must_use_setter(input);
+    let has_ctor_args = !ctor_args_fields(input).is_empty();
+    let reset = match builder_pattern(input) {
+        BuilderPattern::Mutable if has_ctor_args => {
+            let fields = ts_builder_initial_fields(
+                input,
+                |name| quote! { ::std::option::Option::take(&mut self.#name) },
+            );
+            quote! {
+                #vis fn reset(&mut self) -> &mut Self {
+                    *self = #builder_name { #fields };
+                    self
+                }
+            }
+        }
+        BuilderPattern::Owned if has_ctor_args => {
+            let fields = ts_builder_initial_fields(input, |name| quote! { self.#name });
+            quote! {
+                #vis fn reset(self) -> #builder_name #initial_ty_generics {
+                    #builder_name { #fields }
+                }
+            }
+        }
+        BuilderPattern::Mutable => quote! {
+            #vis fn reset(&mut self) -> &mut Self {
+                *self = #builder_name::new();
+                self
+            }
+        },
+        BuilderPattern::Owned => quote! {
+            #vis fn reset(self) -> #builder_name #initial_ty_generics {
+                #builder_name::new()
+            }
+        },
+    };
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #builder_name #ty_generics #where_clause {
+            #doc
+            #must_use
+            #reset
+        }
+    }
+}
+
+/// This function returns `TokenStream` which represents
+/// a code such as
+/// ```ignore
+/// impl CommandBuilder {
+///     pub fn with(&mut self, f: impl FnOnce(&mut Self)) -> &mut Self {
+///         f(self);
+///         self
+///     }
+/// }
+/// ```
 ///
+/// which applies a group of setters without breaking the chain, such as
+/// `.with(|builder| if verbose { builder.arg("-v".to_owned()); })`.
+/// With the owned pattern the closure takes and returns the builder as
+/// `fn with(self, f: impl FnOnce(Self) -> Self) -> Self`.
+/// It is not generated if the setter of a field is named `with`.
+fn ts_builder_impl_with_fn(input: &DeriveInput) -> TokenStream {
+    if is_field_method_name(input, "with") {
+        return TokenStream::new();
+    }
+    let builder_name = builder_name(input);
+    let vis = builder_item_vis(input);
+    let generics = builder_generics(input);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let doc = ts_doc("Applies `f` to the builder.".to_string());
+    let must_use = ts_must_use_setter(input);
+    let with = match builder_pattern(input) {
+        BuilderPattern::Mutable => quote! {
+            #vis fn with(&mut self, f: impl ::std::ops::FnOnce(&mut Self)) -> &mut Self {
+                f(self);
+                self
+            }
+        },
+        BuilderPattern::Owned => quote! {
+            #vis fn with(self, f: impl ::std::ops::FnOnce(Self) -> Self) -> Self {
+                f(self)
+            }
+        },
+    };
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #builder_name #ty_generics #where_clause {
+            #doc
+            #must_use
+            #with
+        }
+    }
+}
+
+/// This function returns `TokenStream` which represents
+/// a code such as
+/// ```ignore
 /// impl CommandBuilder {
-///     fn build(&mut self) -> Result<Command, BuildError> {
-///         Ok(Command {
-///             executable: self
-///                 .executable
-///                 .take()
-///                 .ok_or(BuildError)?,
-///             // `current_dir` is optional field
-///             current_dir: self
-///                 .current_dir
-///                 .take(),
-///         })
+///     pub fn map_timeout(&mut self, f: impl FnOnce(Duration) -> Duration) -> &mut Self {
+///         self.timeout = Option::take(&mut self.timeout).map(f);
+///         self
+///     }
+///
+///     pub fn map_args(&mut self, f: impl FnOnce(&mut Vec<String>)) -> &mut Self {
+///         if self.__set[1] {
+///             f(&mut self.args);
+///         }
+///         self
 ///     }
 /// }
 /// ```
-fn ts_builder_impl_build_fn(input: &DeriveInput) -> TokenStream {
-    let origin_name = origin_name(input);
+///
+/// only if `#[builder(mutators)]` is given, so that a later stage is
+/// able to adjust the value an earlier one set, such as after `merge`.
+/// The closure is only called if the field is set. A collection is
+/// passed by reference so that the items are able to be added or
+/// removed in place. With the owned pattern the methods take and return
+/// `self` as the setters do.
+fn ts_builder_impl_mutators_fn(input: &DeriveInput) -> TokenStream {
+    if !builder_attr_mutators(input) {
+        return TokenStream::new();
+    }
     let builder_name = builder_name(input);
-    let builder_fn_inner: TokenStream = origin_fields(input)
+    let vis = builder_item_vis(input);
+    let generics = builder_generics(input);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (self_ty, ret_ty) = setter_self(input);
+    let must_use = ts_must_use_setter(input);
+    let collection = format_ident!("collection", span = proc_macro2::Span::mixed_site());
+    let mutators = builder_fields(input)
+        .filter(|field| has_mutator(input, field))
         .map(|field| {
             let name = field.ident.as_ref().unwrap();
-            if single_generic_type_of(&field, "Option").is_some() {
-                // optional field
+            let fn_name = mutator_fn_name(&field);
+            let cfgs = field_cfg_attrs(&field);
+            // the type which the builder holds in `Option` or as it is.
+            let ty = optional_type_of(&field).unwrap_or_else(|| field.ty.clone());
+            let is_collection = is_collection_field(&field) || each_item_type(&field).is_some();
+            let (param_ty, body, doc) = if is_collection {
+                let body = if is_collection_field(&field) {
+                    let is_set = ts_is_set(input, &field, quote! { self });
+                    quote! {
+                        if #is_set {
+                            f(&mut self.#name);
+                        }
+                    }
+                } else {
+                    quote! {
+                        if let ::std::option::Option::Some(#collection) = self.#name.as_mut() {
+                            f(#collection);
+                        }
+                    }
+                };
+                (
+                    quote! { impl ::std::ops::FnOnce(&mut #ty) },
+                    body,
+                    format!(
+                        "Applies `f` to `{}` if it is set.",
+                        field_display_name(&field)
+                    ),
+                )
+            } else {
+                (
+                    quote! { impl ::std::ops::FnOnce(#ty) -> #ty },
+                    quote! {
+                        self.#name = ::std::option::Option::take(&mut self.#name).map(f);
+                    },
+                    format!(
+                        "Replaces `{}` with the result of `f` if it is set.",
+                        field_display_name(&field)
+                    ),
+                )
+            };
+            let doc = ts_doc(doc);
+            quote! {
+                #(#cfgs)*
+                #doc
+                #must_use
+                #vis fn #fn_name(#self_ty, f: #param_ty) -> #ret_ty {
+                    #body
+                    self
+                }
+            }
+        });
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #builder_name #ty_generics #where_clause {
+            #(#mutators)*
+        }
+    }
+}
+
+/// Returns whether the collection of the field is in `Option` and the
+/// statements adding `items` into `collection` by the same method as the
+/// `each` method, such as `Extend::extend(collection, items);`, or
+/// `None` if the field is not a collection. `Option<Vec<T>>` is a
+/// collection only with `#[builder(each = "...")]`.
+fn ts_extend_collection(
+    field: &syn::Field,
+    collection: &syn::Ident,
+) -> Option<(bool, TokenStream)> {
+    if !is_collection_field(field) && builder_attr_each(field).is_none() {
+        return None;
+    }
+    let (_, is_optional) = each_item_type(field)?;
+    let via = builder_attr_via(field).and_then(Result::ok);
+    let extend = if builder_attr_map(field) {
+        let insert = via.unwrap_or_else(|| format_ident!("insert"));
+        quote! {
+            for (key, value) in items {
+                #collection.#insert(key, value);
+            }
+        }
+    } else {
+        match via {
+            Some(via) => quote! {
+                for item in items {
+                    #collection.#via(item);
+                }
+            },
+            None => quote! { ::std::iter::Extend::extend(#collection, items); },
+        }
+    };
+    Some((is_optional, extend))
+}
+
+/// This function returns `TokenStream` which represents
+/// a code such as
+/// ```ignore
+/// impl CommandBuilder {
+///     pub fn merge(&mut self, other: CommandBuilder) -> &mut Self {
+///         if other.__set[0] {
+///             self.executable = other.executable;
+///             self.__set[0] = true;
+///         }
+///         if other.__set[1] {
+///             self.args = other.args;
+///             self.__set[1] = true;
+///         }
+///         self
+///     }
+/// }
+/// ```
+///
+/// only if `#[builder(merge)]` is given. The field set in `other`
+/// overrides the one of `self`, including the one which is set to be
+/// `None` or empty. With `#[builder(merge = "append")]`, the items of the
+/// collection are appended to the ones of `self` instead, by the same
+/// method as the `each` method, so that a collection with
+/// `#[builder(via = "...")]` or `#[builder(map)]` must implement
+/// `IntoIterator`.
+fn ts_builder_impl_merge_fn(input: &DeriveInput) -> TokenStream {
+    if !builder_attr_merge(input) {
+        return TokenStream::new();
+    }
+    let builder_name = builder_name(input);
+    let vis = builder_item_vis(input);
+    let generics = builder_generics(input);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (self_ty, ret_ty) = setter_self(input);
+    let must_use = ts_must_use_setter(input);
+    let collection = format_ident!("collection", span = proc_macro2::Span::mixed_site());
+    let merges = builder_fields(input).map(|field| {
+        let name = field.ident.as_ref().unwrap();
+        let cfgs = field_cfg_attrs(&field);
+        let is_set = ts_is_set(input, &field, quote! { other });
+        let mark_set = ts_mark_set(input, &field);
+        // the field set by the setter of `other` counts for `#[builder(once)]`.
+        let mark_set = match once_set_index(input, &field) {
+            Some(index) => {
+                let once_set = once_set_name();
                 quote! {
-                    #name: self.#name.take(),
+                    #mark_set
+                    self.#once_set[#index] |= other.#once_set[#index];
+                }
+            }
+            None => mark_set,
+        };
+        let (is_optional, append) = match ts_extend_collection(&field, &collection) {
+            Some(extend) => extend,
+            // the wrong attributes of a collection are reported by
+            // `validate_fields`.
+            None => {
+                return quote! {
+                    #(#cfgs)*
+                    if #is_set {
+                        self.#name = other.#name;
+                        #mark_set
+                    }
+                };
+            }
+        };
+        if !builder_attr_merge_append(&field) {
+            return quote! {
+                #(#cfgs)*
+                if #is_set {
+                    self.#name = other.#name;
+                    #mark_set
+                }
+            };
+        }
+        if is_optional {
+            let new = ts_new_collection(&optional_type_of(&field).unwrap());
+            quote! {
+                #(#cfgs)*
+                if let ::std::option::Option::Some(items) = other.#name {
+                    let #collection = self.#name.get_or_insert_with(|| #new);
+                    #append
+                    #mark_set
                 }
-            } else if single_generic_type_of(&field, "Vec").is_some() {
+            }
+        } else {
+            quote! {
+                #(#cfgs)*
+                if #is_set {
+                    let items = other.#name;
+                    let #collection = &mut self.#name;
+                    #append
+                    #mark_set
+                }
+            }
+        }
+    });
+    let merge_already_set = if has_once_error(input) {
+        let already_set = already_set_name();
+        quote! {
+            if self.#already_set.is_none() {
+                self.#already_set = other.#already_set;
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #builder_name #ty_generics #where_clause {
+            /// Merges `other` into this builder. The fields set in `other`
+            /// override the ones of this builder unless the items of the
+            /// collections are appended.
+            #must_use
+            #vis fn merge(#self_ty, other: #builder_name #ty_generics) -> #ret_ty {
+                #(#merges)*
+                #merge_already_set
+                self
+            }
+        }
+    }
+}
+
+/// This function returns `TokenStream` which represents
+/// a code such as
+/// ```ignore
+/// impl CommandBuilder {
+///     pub fn build_from_template(&mut self, template: Command) -> Command {
+///         Command {
+///             executable: if self.__set[0] {
+///                 self.executable.take().unwrap()
+///             } else {
+///                 template.executable
+///             },
+///             args: {
+///                 let args = mem::take(&mut self.args);
+///                 if args.is_empty() { template.args } else { args }
+///             },
+///             current_dir: if self.__set[2] {
+///                 self.current_dir.take()
+///             } else {
+///                 template.current_dir
+///             },
+///         }
+///     }
+/// }
+/// ```
+///
+/// only if `#[builder(template)]` is given, which uses the builder as
+/// the patch of `template`. The field which is not set is moved out of
+/// `template`, so that no required field is missing, and so is the
+/// collection which is empty in the builder even if it has been set,
+/// for which the collection must have `is_empty`. With
+/// `#[builder(merge = "append")]`, the items of the collection are
+/// appended to the ones of `template` instead, for which the collection
+/// must implement `IntoIterator`. The defaults are not used at all.
+///
+/// It is named apart from `#[builder(build_with = "...")]`, which
+/// gives the function constructing the struct.
+///
+/// It is opt-in so that the name `build_from_template` is left to the
+/// user and a collection added to by
+/// `#[builder(each = "...", via = "...")]` is not required to have
+/// `is_empty`, and the builder of a variant is not able to move the
+/// fields out of the enum.
+///
+/// `build_from_template` returns `Result` only if some field is
+/// validated by `#[builder(validate = "...")]`, built by
+/// `#[builder(sub_builder)]` or has `#[builder(once = "error")]`, with
+/// the same error as `build`.
+fn ts_builder_impl_build_from_template_fn(input: &DeriveInput) -> TokenStream {
+    if !builder_attr_template(input) {
+        return TokenStream::new();
+    }
+    let origin_name = origin_name(input);
+    let builder_name = builder_name(input);
+    let (_, origin_ty_generics, _) = input.generics.split_for_impl();
+    let generics = builder_generics(input);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (_, build_fn_vis) = builder_attr_build_fn(input)
+        .unwrap_or_else(|_| (format_ident!("build"), builder_item_vis(input)));
+    let self_ty = build_fn_self(input);
+    let build_where_clause = build_fn_where_clause(input);
+    let template = format_ident!("template");
+    let collection = format_ident!("collection", span = proc_macro2::Span::mixed_site());
+
+    let values = origin_fields(input)
+        .map(|field| {
+            let name = field.ident.clone().unwrap();
+            let from_template = quote! { #template.#name };
+            let take = ts_take_field(input, &field);
+            let value = if builder_attr_skip(&field) {
+                from_template
+            } else if builder_attr_sub_builder(&field) {
+                let value = ts_build_sub_builder(take);
+                let init = ts_init_field_error(input, &field);
                 quote! {
-                    #name: std::mem::replace(&mut self.#name, std::vec::Vec::new()),
+                    match #value {
+                        ::std::option::Option::Some(::std::result::Result::Ok(value)) => value,
+                        ::std::option::Option::Some(::std::result::Result::Err(source)) => {
+                            return ::std::result::Result::Err(#init);
+                        }
+                        ::std::option::Option::None => #from_template,
+                    }
+                }
+            } else if let Some((is_optional, extend)) = ts_extend_collection(&field, &collection) {
+                let append = builder_attr_merge_append(&field);
+                if is_optional && append {
+                    let new = ts_new_collection(&optional_type_of(&field).unwrap());
+                    quote! {
+                        match #take {
+                            ::std::option::Option::Some(items) => {
+                                let mut #collection =
+                                    ::std::option::Option::unwrap_or_else(#from_template, || #new);
+                                {
+                                    let #collection = &mut #collection;
+                                    #extend
+                                }
+                                ::std::option::Option::Some(#collection)
+                            }
+                            ::std::option::Option::None => #from_template,
+                        }
+                    }
+                } else if is_optional {
+                    quote! {
+                        ::std::option::Option::filter(#take, |#collection| !#collection.is_empty())
+                            .or(#from_template)
+                    }
+                } else if append {
+                    quote! {
+                        {
+                            let items = #take;
+                            let mut #collection = #from_template;
+                            {
+                                let #collection = &mut #collection;
+                                #extend
+                            }
+                            #collection
+                        }
+                    }
+                } else {
+                    quote! {
+                        {
+                            let #collection = #take;
+                            if #collection.is_empty() {
+                                #from_template
+                            } else {
+                                #collection
+                            }
+                        }
+                    }
                 }
+            } else if is_collection_field(&field) {
+                // the wrong attributes are reported by `validate_fields`.
+                take
             } else {
-                // required field
+                let is_set = ts_is_set(input, &field, quote! { self });
+                let take = if optional_type_of(&field).is_some() {
+                    take
+                } else {
+                    quote! { ::std::option::Option::unwrap_or_else(#take, || ::std::unreachable!()) }
+                };
                 quote! {
-                    #name: self.#name.take().ok_or(BuildError())?,
+                    if #is_set {
+                        #take
+                    } else {
+                        #from_template
+                    }
                 }
+            };
+            match builder_attr_validate(&field) {
+                Some(Ok(validate)) => {
+                    let invalid = ts_invalid_field_error(input, &field);
+                    let value = quote! {
+                        {
+                            let value = #value;
+                            if let ::std::result::Result::Err(message) = #validate(&value) {
+                                return ::std::result::Result::Err(#invalid);
+                            }
+                            value
+                        }
+                    };
+                    (name, value)
+                }
+                _ => (name, value),
             }
         })
         .collect();
+    let origin = ts_construct_origin(input, values);
+    let built_ty = ts_built_type(input);
+    let is_fallible = has_once_error(input)
+        || origin_fields(input).any(|field| {
+            builder_attr_validate(&field).is_some() || builder_attr_sub_builder(&field)
+        });
+    let check_already_set = ts_check_already_set(input, None);
 
-    quote! {
-        #[derive(Debug)]
-        pub struct BuildError();
+    if !is_fallible {
+        let doc = ts_doc(format!(
+            "Builds `{}`, taking the fields which are not set from `template`.",
+            origin_name
+        ));
+        let must_use = ts_must_use_build(input);
+        return quote! {
+            #[automatically_derived]
+            impl #impl_generics #builder_name #ty_generics #where_clause {
+                #doc
+                #must_use
+                #build_fn_vis fn build_from_template(
+                    #self_ty,
+                    #template: #origin_name #origin_ty_generics,
+                ) -> #built_ty
+                #build_where_clause
+                {
+                    #origin
+                }
+            }
+        };
+    }
+
+    let error_ty = match builder_attr_error(input) {
+        Some(Ok(error)) => quote! { #error },
+        _ => {
+            let build_error = build_error_name(input);
+            quote! { #build_error }
+        }
+    };
+    let doc = ts_doc(format!(
+        "Builds `{}`, taking the fields which are not set from `template`, \
+         or returns an error if a value is invalid.",
+        origin_name
+    ));
 
-        impl #builder_name {
-            fn build(&mut self) -> std::result::Result<#origin_name, BuildError>
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #builder_name #ty_generics #where_clause {
+            #doc
+            #build_fn_vis fn build_from_template(
+                #self_ty,
+                #template: #origin_name #origin_ty_generics,
+            ) -> ::std::result::Result<#built_ty, #error_ty>
+            #build_where_clause
             {
-                Ok(#origin_name {
-                    #builder_fn_inner
-                })
+                #check_already_set
+                ::std::result::Result::Ok(#origin)
+            }
+        }
+    }
+}
+
+/// This function returns `TokenStream` which represents
+/// a code such as
+/// ```ignore
+/// impl Debug for CommandBuilder {
+///     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+///         let mut debug = f.debug_struct("CommandBuilder");
+///         match self.executable {
+///             Some(ref value) if self.__set[0] => debug.field("executable", value),
+///             _ => debug.field("executable", &format_args!("<unset>")),
+///         };
+///         match self.password {
+///             Some(_) if self.__set[1] => debug.field("password", &format_args!("<redacted>")),
+///             _ => debug.field("password", &format_args!("<unset>")),
+///         };
+///         debug.field("args", &self.args);
+///         debug.finish()
+///     }
+/// }
+/// ```
+///
+/// only if `#[builder(debug)]` is given. The value of the field with
+/// `#[builder(sensitive)]` is printed as `<redacted>`. As `derive(Debug)`
+/// does, every type parameter of the struct is bounded by `Debug`.
+fn ts_builder_impl_debug(input: &DeriveInput) -> TokenStream {
+    if !builder_attr_debug(input) {
+        return TokenStream::new();
+    }
+    let builder_name = builder_name(input);
+    let builder_name_str = builder_export_name(input).to_string();
+    let mut generics = builder_generics(input);
+    let debug_bounds: Vec<syn::WherePredicate> = generics
+        .type_params()
+        .map(|param| {
+            let ident = &param.ident;
+            syn::parse_quote!(#ident: ::std::fmt::Debug)
+        })
+        .collect();
+    generics.make_where_clause().predicates.extend(debug_bounds);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let debug = format_ident!("debug", span = proc_macro2::Span::mixed_site());
+    let fields = builder_fields(input).map(|field| {
+        let name = field.ident.as_ref().unwrap();
+        let name_str = field_display_name(&field);
+        let cfgs = field_cfg_attrs(&field);
+        let sensitive = builder_attr_sensitive(&field);
+        if is_collection_field(&field) {
+            let value = if sensitive {
+                quote! { &::std::format_args!("<redacted>") }
+            } else {
+                quote! { &self.#name }
+            };
+            return quote! {
+                #(#cfgs)*
+                #debug.field(#name_str, #value);
+            };
+        }
+        let is_set = ts_is_set(input, &field, quote! { self });
+        let set = if sensitive {
+            quote! {
+                ::std::option::Option::Some(_) if #is_set => {
+                    #debug.field(#name_str, &::std::format_args!("<redacted>"))
+                }
+            }
+        } else {
+            quote! {
+                ::std::option::Option::Some(ref value) if #is_set => #debug.field(#name_str, value),
+            }
+        };
+        quote! {
+            #(#cfgs)*
+            match self.#name {
+                #set
+                _ => #debug.field(#name_str, &::std::format_args!("<unset>")),
+            };
+        }
+    });
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::std::fmt::Debug for #builder_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                let mut #debug = f.debug_struct(#builder_name_str);
+                #(#fields)*
+                #debug.finish()
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option_type_in(ty: syn::Type) -> Option<syn::Type> {
+        single_generic_type_in(&ty, "Option")
+    }
+
+    #[test]
+    fn single_generic_type_in_option() {
+        let ty = option_type_in(syn::parse_quote!(Option<String>));
+        assert_eq!(ty, Some(syn::parse_quote!(String)));
+    }
+
+    #[test]
+    fn single_generic_type_in_tuple_arg() {
+        let ty = syn::parse_quote!(Vec<(String, u32)>);
+        let item = single_generic_type_in(&ty, "Vec");
+        assert_eq!(item, Some(syn::parse_quote!((String, u32))));
+    }
+
+    #[test]
+    fn single_generic_type_in_option_without_args() {
+        assert_eq!(option_type_in(syn::parse_quote!(Option)), None);
+        assert_eq!(option_type_in(syn::parse_str("Option<>").unwrap()), None);
+        assert_eq!(option_type_in(syn::parse_quote!(Option<'a>)), None);
+        assert_eq!(option_type_in(syn::parse_quote!(Option<String, i32>)), None);
+    }
+
+    #[test]
+    fn single_generic_type_in_parenthesized_args() {
+        assert_eq!(option_type_in(syn::parse_quote!(Option(String))), None);
+        assert_eq!(
+            option_type_in(syn::parse_quote!(Fn(String) -> Option<i32>)),
+            None
+        );
+        let ty = syn::parse_quote!(Box<dyn Fn(String) -> Option<i32>>);
+        assert_eq!(single_generic_type_in(&ty, "Fn"), None);
+    }
+
+    #[test]
+    fn single_generic_type_in_other_paths() {
+        assert_eq!(
+            option_type_in(syn::parse_quote!(std::option::Option<i32>)),
+            None
+        );
+        assert_eq!(option_type_in(syn::parse_quote!(::Option<i32>)), None);
+        assert_eq!(option_type_in(syn::parse_quote!(Option<i32>::Item)), None);
+        assert_eq!(
+            option_type_in(syn::parse_quote!(<T as Trait>::Option<i32>)),
+            None
+        );
+    }
+
+    #[test]
+    fn single_generic_type_in_non_path_types() {
+        assert_eq!(option_type_in(syn::parse_quote!(&Option<i32>)), None);
+        assert_eq!(option_type_in(syn::parse_quote!(&'a mut Option<i32>)), None);
+        assert_eq!(option_type_in(syn::parse_quote!([Option<i32>; 3])), None);
+        assert_eq!(option_type_in(syn::parse_quote!([Option<i32>])), None);
+        assert_eq!(option_type_in(syn::parse_quote!((Option<i32>,))), None);
+        assert_eq!(option_type_in(syn::parse_quote!(option_type!())), None);
+        assert_eq!(option_type_in(syn::parse_quote!(fn() -> Option<i32>)), None);
+    }
+
+    // The spans have locations only for the input parsed from a string.
+    fn parse_input(source: &str) -> (DeriveInput, syn::Field) {
+        let input: DeriveInput = syn::parse_str(source).unwrap();
+        let field = origin_fields(&input).next().unwrap();
+        (input, field)
+    }
+
+    fn start(span: proc_macro2::Span) -> (usize, usize) {
+        let start = span.start();
+        (start.line, start.column)
+    }
+
+    #[test]
+    fn setter_name_is_spanned_at_the_field() {
+        let (input, field) = parse_input(
+            r#"
+            #[builder(prefix = "with_")]
+            struct Command {
+                executable: String,
+            }
+            "#,
+        );
+        let ident = field.ident.as_ref().unwrap();
+        assert_eq!(setter_name(&input, &field), "with_executable");
+        assert_eq!(
+            start(setter_name(&input, &field).span()),
+            start(ident.span())
+        );
+    }
+
+    #[test]
+    fn renamed_setter_name_is_spanned_at_the_literal() {
+        let (input, field) = parse_input(
+            r#"
+            struct Command {
+                #[builder(rename = "exe")]
+                executable: String,
+            }
+            "#,
+        );
+        let rename = find_builder_attr_str(&field.attrs, "rename").unwrap();
+        assert_eq!(
+            start(setter_name(&input, &field).span()),
+            start(rename.span())
+        );
+    }
+
+    #[test]
+    fn each_fn_names_are_spanned_at_the_literal_and_the_field() {
+        let (input, field) = parse_input(
+            r#"
+            #[builder(each_prefix = "add_")]
+            struct Command {
+                #[builder(each = "arg")]
+                args: Vec<String>,
+            }
+            "#,
+        );
+        let each = find_builder_attr_str(&field.attrs, "each").unwrap();
+        let each_fn_name = each_fn_name(&input, &field).unwrap();
+        assert_eq!(each_fn_name, "add_arg");
+        assert_eq!(start(each_fn_name.span()), start(each.span()));
+
+        let ident = field.ident.as_ref().unwrap();
+        let each_all_fn_name = each_all_fn_name(&input, &field).unwrap();
+        assert_eq!(each_all_fn_name, "add_args_all");
+        assert_eq!(start(each_all_fn_name.span()), start(ident.span()));
+    }
+
+    #[test]
+    fn other_attributes_are_ignored() {
+        let (input, field) = parse_input(
+            r#"
+            #[repr(C)]
+            #[serde(rename_all = "camelCase")]
+            struct Command {
+                #[serde(rename = "each", default)]
+                #[builder(each = "arg")]
+                #[doc = "args"]
+                args: Vec<String>,
+            }
+            "#,
+        );
+        assert!(validate_container_attrs(&input).is_ok());
+        assert_eq!(get_builder_meta_items(&input.attrs).count(), 0);
+        assert_eq!(get_builder_meta_items(&field.attrs).count(), 1);
+        assert_eq!(builder_attr_each(&field).unwrap().value(), "arg");
+    }
+}
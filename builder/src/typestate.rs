@@ -0,0 +1,319 @@
+//! Code generation for `#[builder(typestate)]`.
+//!
+//! The builder tracks whether each required field is set by a const
+//! parameter, such as `CommandBuilder<const EXECUTABLE_SET: bool>`.
+//! The setter of a required field turns its parameter into `true`
+//! and `build` is only implemented for the builder whose parameters
+//! are all `true`, so that a missing required field is reported at
+//! compile time instead of by `CommandBuilderError`.
+
+use super::*;
+
+/// Look for `#[builder(typestate)]` attribute on the struct.
+pub(crate) fn builder_attr_typestate(input: &DeriveInput) -> bool {
+    get_builder_meta_items(&input.attrs).any(|meta| match meta {
+        syn::NestedMeta::Meta(syn::Meta::Path(ref path)) => is_path_eq(path, "typestate"),
+        _ => false,
+    })
+}
+
+/// Reject the struct level attributes which make no sense for the
+/// type-state builder, whose `build` never fails and whose setters
+/// always take `self` by value.
+pub(crate) fn validate(input: &DeriveInput) -> Result<(), syn::Error> {
+    if !builder_attr_typestate(input) {
+        return Ok(());
+    }
+    for meta in get_builder_meta_items(&input.attrs) {
+        let path = match meta {
+            syn::NestedMeta::Meta(ref meta) => meta.path(),
+            _ => continue,
+        };
+        let conflicts = ["error", "panic_helper", "fallible", "error_derive"]
+            .iter()
+            .any(|key| is_path_eq(path, key));
+        if conflicts {
+            return Err(syn::Error::new_spanned(
+                meta,
+                "this attribute is not able to be used with `builder(typestate)` \
+                 because its `build` never fails",
+            ));
+        }
+    }
+    if let Some(path) = find_builder_attr_path(&input.attrs, "merge") {
+        return Err(syn::Error::new_spanned(
+            path,
+            "`builder(merge)` is not able to be used with `builder(typestate)` \
+             because the builders to be merged have different types",
+        ));
+    }
+    for field in origin_fields(input) {
+        if let Some(path) = find_builder_attr_path(&field.attrs, "sub_builder") {
+            return Err(syn::Error::new_spanned(
+                path,
+                "`builder(sub_builder)` is not able to be used with `builder(typestate)` \
+                 because its `build` never fails",
+            ));
+        }
+    }
+    if let Some(path) = find_builder_attr_path(&input.attrs, "clone_build") {
+        return Err(syn::Error::new_spanned(
+            path,
+            "`builder(clone_build)` is not able to be used with `builder(typestate)` \
+             because its `build` takes the builder by value",
+        ));
+    }
+    // The parameters of the builder are not able to be removed by `cfg`
+    // at the places where the builder type is written.
+    for field in state_fields(input) {
+        if let Some(cfg) = field_cfg_attrs(&field).first() {
+            return Err(syn::Error::new_spanned(
+                cfg,
+                "#[cfg] attribute on a required field is not supported with `builder(typestate)`",
+            ));
+        }
+    }
+    for field in origin_fields(input) {
+        for key in &["validate", "default_try"] {
+            if let Some(s) = find_builder_attr_str(&field.attrs, key) {
+                return Err(syn::Error::new_spanned(
+                    s,
+                    format!(
+                        "`builder({})` is not able to be used with `builder(typestate)` \
+                         because its `build` never fails",
+                        key
+                    ),
+                ));
+            }
+        }
+        if let Some(Ok(OnceMode::Error)) = builder_attr_once(&field) {
+            let s = find_builder_attr_str(&field.attrs, "once").unwrap();
+            return Err(syn::Error::new_spanned(
+                s,
+                "`builder(once = \"error\")` is not able to be used with `builder(typestate)` \
+                 because its `build` never fails",
+            ));
+        }
+    }
+    if let Ok(BuilderPattern::Mutable) = builder_attr_pattern(input) {
+        if let Some(s) = find_builder_attr_str(&input.attrs, "pattern") {
+            return Err(syn::Error::new_spanned(
+                s,
+                "`builder(typestate)` always takes `self` by value",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the name of the const parameter tracking the field,
+/// such as `EXECUTABLE_SET` for `executable`.
+fn state_param_name(field: &syn::Field) -> syn::Ident {
+    let name = field.ident.as_ref().unwrap().unraw().to_string();
+    format_ident!("{}_SET", name.to_uppercase())
+}
+
+/// Returns the fields which are tracked by the type of the builder.
+/// The fields with `#[builder(ctor_args)]` are not, because they are
+/// set by `new`.
+fn state_fields(input: &DeriveInput) -> Vec<syn::Field> {
+    if !builder_attr_typestate(input) {
+        return Vec::new();
+    }
+    origin_fields(input)
+        .filter(|field| is_required_field(field) && !builder_attr_ctor_args(field))
+        .collect()
+}
+
+/// Returns the const parameters of the builder such as
+/// `const EXECUTABLE_SET: bool`.
+pub(crate) fn state_params(input: &DeriveInput) -> Vec<syn::GenericParam> {
+    state_fields(input)
+        .iter()
+        .map(|field| {
+            let param = state_param_name(field);
+            syn::parse_quote!(const #param: bool)
+        })
+        .collect()
+}
+
+/// Returns `false` for each const parameter of the builder.
+pub(crate) fn initial_state_args(input: &DeriveInput) -> Vec<TokenStream> {
+    state_fields(input)
+        .iter()
+        .map(|_| quote! { false })
+        .collect()
+}
+
+/// Returns `true` for each const parameter of the builder, which is
+/// the builder whose required fields are all set.
+pub(crate) fn complete_state_args(input: &DeriveInput) -> Vec<TokenStream> {
+    state_fields(input)
+        .iter()
+        .map(|_| quote! { true })
+        .collect()
+}
+
+/// This function returns `TokenStream` which represents
+/// a code such as
+/// ```ignore
+/// impl<const EXECUTABLE_SET: bool, const PROGRAM_SET: bool>
+///     CommandBuilder<EXECUTABLE_SET, PROGRAM_SET>
+/// {
+///     pub fn executable(self, item: String) -> CommandBuilder<true, PROGRAM_SET> {
+///         CommandBuilder {
+///             executable: Some(item),
+///             program: self.program,
+///             current_dir: self.current_dir,
+///             __set: {
+///                 let mut flags = self.__set;
+///                 flags[0] = true;
+///                 flags
+///             },
+///         }
+///     }
+///     ...
+/// }
+/// ```
+///
+/// for the required fields. The setters of the other fields are
+/// generated by `ts_builder_impl_fields_fn` as the owned pattern.
+pub(crate) fn ts_builder_impl_required_fields_fn(input: &DeriveInput) -> TokenStream {
+    let fields = state_fields(input);
+    if fields.is_empty() {
+        return TokenStream::new();
+    }
+    let builder_name = builder_name(input);
+    let vis = builder_item_vis(input);
+    let generics = builder_generics(input);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let all_fields: Vec<syn::Field> = builder_fields(input).collect();
+    let must_use = ts_must_use_builder(input);
+
+    let setters: TokenStream = fields
+        .iter()
+        .map(|field| {
+            let name = field.ident.as_ref().unwrap();
+            let setter_name = setter_name(input, field);
+            let (arg_ty, item) = setter_arg(input, field);
+            let state_args = fields.iter().map(|other| {
+                if other.ident == field.ident {
+                    quote! { true }
+                } else {
+                    let param = state_param_name(other);
+                    quote! { #param }
+                }
+            });
+            let ret_ty_args = origin_generic_args(input).into_iter().chain(state_args);
+            let doc = ts_doc(format!("Sets `{}`.", field_display_name(field)));
+            let check_once = ts_check_once(input, field);
+            let moved_fields = all_fields.iter().map(|other| {
+                let other_name = other.ident.as_ref().unwrap();
+                if other_name == name {
+                    quote! { #name: ::std::option::Option::Some(#item), }
+                } else {
+                    let cfgs = field_cfg_attrs(other);
+                    quote! { #(#cfgs)* #other_name: self.#other_name, }
+                }
+            });
+            let set_flags = set_flags_name();
+            let index = set_flag_index(input, field);
+            let flags = syn::Ident::new("flags", proc_macro2::Span::mixed_site());
+            let once_set = if once_fields(input).next().is_some() {
+                let once_set = once_set_name();
+                quote! { #once_set: self.#once_set, }
+            } else {
+                TokenStream::new()
+            };
+            // `check_once` marks the field in `self`, which is moved into the
+            // new builder.
+            let self_ty = if check_once.is_empty() {
+                quote! { self }
+            } else {
+                quote! { mut self }
+            };
+
+            quote! {
+                #doc
+                #must_use
+                #vis fn #setter_name(#self_ty, item: #arg_ty) -> #builder_name<#(#ret_ty_args),*> {
+                    #check_once
+                    #builder_name {
+                        #(#moved_fields)*
+                        #set_flags: {
+                            let mut #flags = self.#set_flags;
+                            #flags[#index] = true;
+                            #flags
+                        },
+                        #once_set
+                    }
+                }
+            }
+        })
+        .collect();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #builder_name #ty_generics #where_clause {
+            #setters
+        }
+    }
+}
+
+/// This function returns `TokenStream` which represents
+/// a code such as
+/// ```ignore
+/// impl CommandBuilder<true, true> {
+///     pub fn build(self) -> Command {
+///         Command {
+///             executable: self.executable.unwrap(),
+///             program: self.program.unwrap(),
+///             current_dir: self.current_dir,
+///         }
+///     }
+/// }
+/// ```
+///
+/// where `unwrap` never panics because the setters of the required
+/// fields have been called.
+pub(crate) fn ts_builder_impl_build_fn(input: &DeriveInput) -> TokenStream {
+    if !builder_attr_typestate(input) {
+        return TokenStream::new();
+    }
+    let origin_name = origin_name(input);
+    let builder_name = builder_name(input);
+    let (impl_generics, _, where_clause) = input.generics.split_for_impl();
+    let (build_fn_name, build_fn_vis) = builder_attr_build_fn(input)
+        .unwrap_or_else(|_| (format_ident!("build"), builder_item_vis(input)));
+    let builder_ty_args = origin_generic_args(input)
+        .into_iter()
+        .chain(complete_state_args(input));
+    let values = origin_fields(input)
+        .map(|field| {
+            let name = field.ident.clone().unwrap();
+            if is_required_field(&field) {
+                (name.clone(), quote! { self.#name.unwrap() })
+            } else {
+                let value = ts_default_field(input, &field, quote! { self.#name });
+                (name.clone(), value)
+            }
+        })
+        .collect();
+    let origin = ts_construct_origin(input, values);
+    let defaults = ts_builder_defaults(input);
+    let built_ty = ts_built_type(input);
+    let doc = ts_doc(format!("Builds `{}`.", origin_name));
+    let must_use = ts_must_use_build(input);
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #builder_name<#(#builder_ty_args),*> #where_clause {
+            #doc
+            #must_use
+            #build_fn_vis fn #build_fn_name(self) -> #built_ty {
+                #defaults
+                #origin
+            }
+        }
+    }
+}
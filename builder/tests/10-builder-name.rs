@@ -0,0 +1,29 @@
+// The builder is named `{Struct}Builder` by default, which collides with any
+// hand-written type of the same name in the module. The struct-level
+// attribute #[builder(name = "...")] renames the generated builder and every
+// reference to it.
+
+use derive_builder::Builder;
+
+#[allow(dead_code)]
+pub struct CommandBuilder;
+
+#[derive(Builder)]
+#[builder(name = "CommandSpec")]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let mut builder: CommandSpec = Command::builder();
+    builder.executable("cargo".to_owned());
+    builder.arg("build".to_owned());
+
+    let command = builder.build().unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build"]);
+    assert!(command.current_dir.is_none());
+}
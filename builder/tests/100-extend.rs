@@ -0,0 +1,44 @@
+// The builder implements `Extend` for the items of the collection when exactly
+// one field has #[builder(each = "...")], so that the items of an iterator are
+// able to be added by `extend` or passed to a function taking `impl Extend`.
+// A map with #[builder(map)] is extended by the pairs of the keys and the
+// values.
+
+use derive_builder::Builder;
+use std::collections::HashMap;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+}
+
+#[derive(Builder)]
+#[builder(pattern = "owned")]
+pub struct Env {
+    #[builder(each = "var", map)]
+    vars: HashMap<String, String>,
+}
+
+fn collect_args(sink: &mut impl Extend<String>) {
+    sink.extend(vec!["--release".to_owned(), "--locked".to_owned()]);
+}
+
+fn main() {
+    let mut builder = Command::builder();
+    builder.executable("cargo".to_owned()).arg("build".to_owned());
+    collect_args(&mut builder);
+    let command = builder.build().unwrap();
+
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build", "--release", "--locked"]);
+    assert_eq!(command.current_dir, None);
+
+    let mut builder = Env::builder();
+    builder.extend(vec![("RUST_LOG".to_owned(), "debug".to_owned())]);
+    let env = builder.build();
+
+    assert_eq!(env.vars["RUST_LOG"], "debug");
+}
@@ -0,0 +1,54 @@
+// #[builder(ctor_args)] makes the field a parameter of `builder`, so that an
+// obviously required field is given up front instead of being checked by
+// `build`. Several fields become the parameters in declaration order, and
+// their setters are still generated to override the values. With
+// #[builder(typestate)] the fields are not tracked by the type of the builder.
+
+use derive_builder::Builder;
+
+#[derive(Builder, Debug)]
+pub struct Command {
+    #[builder(ctor_args)]
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[builder(ctor_args)]
+    timeout: u64,
+    current_dir: Option<String>,
+}
+
+#[derive(Builder)]
+#[builder(typestate)]
+pub struct Job {
+    #[builder(ctor_args, setter(skip))]
+    name: String,
+    priority: u8,
+    tag: Option<String>,
+}
+
+fn main() {
+    let command = Command::builder("cargo".to_owned(), 30)
+        .arg("build".to_owned())
+        .build()
+        .unwrap();
+
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build"]);
+    assert_eq!(command.timeout, 30);
+    assert_eq!(command.current_dir, None);
+
+    let mut builder = CommandBuilder::new("cargo".to_owned(), 30);
+    assert!(builder.is_complete());
+    builder.executable("rustc".to_owned()).arg("--version".to_owned());
+    let command = builder.reset().timeout(60).build().unwrap();
+
+    assert_eq!(command.executable, "rustc");
+    assert!(command.args.is_empty());
+    assert_eq!(command.timeout, 60);
+
+    let job = Job::builder("nightly".to_owned()).priority(1).build();
+
+    assert_eq!(job.name, "nightly");
+    assert_eq!(job.priority, 1);
+    assert_eq!(job.tag, None);
+}
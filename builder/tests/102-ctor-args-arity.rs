@@ -0,0 +1,17 @@
+// The fields with #[builder(ctor_args)] are the parameters of `builder`, so
+// that calling it without them is an error at compile time.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(ctor_args)]
+    executable: String,
+    #[builder(ctor_args)]
+    timeout: u64,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let _ = Command::builder("cargo".to_owned()).build();
+}
@@ -0,0 +1,65 @@
+// #[builder(sub_builder)] on a field whose type also derives `Builder` makes
+// the setter take a closure configuring the builder of the field, named
+// `{Type}Builder` as usual. The sub-builder is built by `build` of the outer
+// builder, so that its error is returned as `InitError` of the field together
+// with the other errors.
+
+use derive_builder::Builder;
+use std::error::Error;
+
+#[derive(Builder, Debug, PartialEq)]
+pub struct ListenerConfig {
+    host: String,
+    port: u16,
+    backlog: Option<u32>,
+}
+
+#[derive(Builder, Debug, PartialEq)]
+pub struct Server {
+    #[builder(sub_builder)]
+    listener: ListenerConfig,
+    name: String,
+}
+
+fn main() {
+    let server = Server::builder()
+        .listener(|listener| {
+            listener.host("localhost".to_owned());
+        })
+        .name("api".to_owned())
+        .listener(|listener| {
+            listener.port(8080);
+        })
+        .build()
+        .unwrap();
+
+    assert_eq!(server.name, "api");
+    assert_eq!(
+        server.listener,
+        ListenerConfig {
+            host: "localhost".to_owned(),
+            port: 8080,
+            backlog: None,
+        }
+    );
+
+    let err = Server::builder()
+        .listener(|listener| {
+            listener.host("localhost".to_owned());
+        })
+        .name("api".to_owned())
+        .build()
+        .unwrap_err();
+
+    assert_eq!(err.fields(), ["listener"]);
+    assert_eq!(
+        err.to_string(),
+        "field `listener` failed to be initialized: field `port` was not set"
+    );
+    assert!(err.source().unwrap().is::<ListenerConfigBuilderError>());
+
+    let errors = Server::builder().try_build().unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].fields(), ["listener"]);
+    assert_eq!(errors[1].fields(), ["name"]);
+}
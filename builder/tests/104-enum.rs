@@ -0,0 +1,52 @@
+// An enum whose variants all have named fields gets a builder for each
+// variant, named `{Enum}{Variant}Builder`, whose `build` returns the enum.
+// `builder()` returns the builder selecting the variant by the method named
+// after it in snake case. The attributes on the enum apply to every variant,
+// and the ones on a variant only to its builder.
+
+use derive_builder::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+pub enum Command {
+    Run {
+        executable: String,
+        #[builder(each = "arg")]
+        args: Vec<String>,
+        current_dir: Option<String>,
+    },
+    #[builder(pattern = "owned")]
+    SendSignal {
+        #[builder(ctor_args)]
+        pid: u32,
+        #[builder(default = 15)]
+        signal: i32,
+    },
+}
+
+fn main() {
+    let run = Command::builder()
+        .run()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        run,
+        Command::Run {
+            executable: "cargo".to_owned(),
+            args: vec!["build".to_owned()],
+            current_dir: None,
+        }
+    );
+
+    let send = Command::builder()
+        .send_signal(42)
+        .signal(9)
+        .build()
+        .unwrap();
+    assert_eq!(send, Command::SendSignal { pid: 42, signal: 9 });
+
+    let err = CommandRunBuilder::new().build().unwrap_err();
+    assert_eq!(err.to_string(), "field `executable` was not set");
+}
@@ -0,0 +1,30 @@
+// #[builder(setter(transform = "..."))] applies the closure to the argument of
+// the setter before it is stored, and the setter takes the type of the
+// parameter of the closure, which must be written.
+
+use derive_builder::Builder;
+use std::path::PathBuf;
+
+#[derive(Builder)]
+#[builder(setter(into))]
+pub struct Command {
+    #[builder(setter(transform = "|s: &str| s.trim().to_owned()"))]
+    executable: String,
+    #[builder(setter(transform = "|parts: &[&str]| parts.iter().collect()"))]
+    current_dir: Option<PathBuf>,
+    #[builder(setter(transform = "|secs: u64| std::time::Duration::from_secs(secs)"))]
+    timeout: std::time::Duration,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("  cargo\n")
+        .current_dir(&["/", "home", "user"])
+        .timeout(30)
+        .build()
+        .unwrap();
+
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.current_dir, Some(PathBuf::from("/home/user")));
+    assert_eq!(command.timeout.as_millis(), 30_000);
+}
@@ -0,0 +1,53 @@
+// #[builder(flag)] on a `bool` field makes its setter take no argument and set
+// `true`, as the flags of a command line, and the field is `false` unless it
+// is called. `set_{field}` takes the value for explicit control.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(flag)]
+    verbose: bool,
+    #[builder(flag, rename = "release_mode")]
+    release: bool,
+    #[builder(flag, default = true)]
+    color: bool,
+}
+
+#[derive(Builder)]
+#[builder(pattern = "owned", prefix = "with_")]
+pub struct Options {
+    #[builder(flag)]
+    quiet: bool,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .verbose()
+        .build()
+        .unwrap();
+
+    assert!(command.verbose);
+    assert!(!command.release);
+    assert!(command.color);
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .release_mode()
+        .set_color(false)
+        .set_verbose(true)
+        .set_verbose(false)
+        .build()
+        .unwrap();
+
+    assert!(!command.verbose);
+    assert!(command.release);
+    assert!(!command.color);
+
+    let options = Options::builder().with_quiet().build();
+    assert!(options.quiet);
+    let options = Options::builder().set_quiet(false).build();
+    assert!(!options.quiet);
+}
@@ -0,0 +1,49 @@
+// #[builder(wrap = "...")] makes `build` return the struct wrapped by `Arc`,
+// `Rc` or `Box`, so that a value which is shared or boxed is built without
+// wrapping it at every call site.
+
+use derive_builder::Builder;
+use std::convert::TryFrom;
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[derive(Builder)]
+#[builder(wrap = "Arc")]
+pub struct Command {
+    executable: String,
+    args: Vec<String>,
+}
+
+#[derive(Builder)]
+#[builder(wrap = "Rc", pattern = "owned")]
+pub struct Options {
+    verbose: Option<bool>,
+}
+
+#[derive(Builder)]
+#[builder(wrap = "Box", try_from)]
+pub struct Node<T> {
+    value: T,
+}
+
+fn main() {
+    let command: Arc<Command> = Command::builder()
+        .executable("cargo".to_owned())
+        .args(vec!["build".to_owned()])
+        .build()
+        .unwrap();
+    let shared = Arc::clone(&command);
+    assert_eq!(shared.executable, "cargo");
+    assert_eq!(command.args, vec!["build"]);
+
+    let options: Rc<Options> = Options::builder().verbose(true).build();
+    assert_eq!(options.verbose, Some(true));
+
+    let node: Box<Node<i32>> = Node::builder().value(1).build().unwrap();
+    assert_eq!(node.value, 1);
+
+    let mut builder = Node::builder();
+    builder.value("leaf");
+    let node = Box::<Node<&str>>::try_from(builder).unwrap();
+    assert_eq!(node.value, "leaf");
+}
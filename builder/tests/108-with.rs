@@ -0,0 +1,56 @@
+// `with` applies a closure to the builder, so that a group of setters is
+// applied conditionally without breaking the chain of the setters. The
+// closure takes `&mut Self`, or takes and returns `Self` with the owned
+// pattern.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+}
+
+#[derive(Builder)]
+#[builder(pattern = "owned", name = "OptionsConfig", vis = "pub(crate)")]
+pub struct Options {
+    verbose: Option<bool>,
+    jobs: Option<u32>,
+}
+
+#[derive(Builder)]
+#[builder(typestate)]
+pub struct Job {
+    name: String,
+    priority: Option<u8>,
+}
+
+fn main() {
+    let verbose = true;
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .with(|builder| {
+            if verbose {
+                builder.arg("-v".to_owned()).arg("--color".to_owned());
+            }
+        })
+        .arg("build".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.args, vec!["-v", "--color", "build"]);
+
+    let options = Options::builder()
+        .with(|builder: OptionsConfig| builder.verbose(true).jobs(4))
+        .build();
+    assert_eq!(options.verbose, Some(true));
+    assert_eq!(options.jobs, Some(4));
+
+    let job = Job::builder()
+        .with(|builder| builder.priority(1))
+        .name("test".to_owned())
+        .with(|builder| builder)
+        .build();
+    assert_eq!(job.name, "test");
+    assert_eq!(job.priority, Some(1));
+}
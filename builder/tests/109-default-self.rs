@@ -0,0 +1,60 @@
+// The expression of #[builder(default = ...)] is able to read the other
+// fields of the builder by `self`, which are the values set by the setters.
+// Such defaults are evaluated before any value is taken out of the builder,
+// so that the order of the fields does not matter.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(default = "self.executable.clone().unwrap_or_default()")]
+    program: String,
+    executable: String,
+    #[builder(default = "self.program.clone().or_else(|| self.executable.clone())")]
+    title: Option<String>,
+    #[builder(default = "self.args.len()")]
+    arg_count: usize,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+}
+
+#[derive(Builder)]
+#[builder(pattern = "owned")]
+pub struct Server {
+    #[builder(default = "self.port.map_or(false, |port| port == 443)")]
+    tls: bool,
+    port: Option<u16>,
+    #[builder(skip, default = "self.port.is_some()")]
+    explicit_port: bool,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .arg("--release".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.program, "cargo");
+    assert_eq!(command.title.as_deref(), Some("cargo"));
+    assert_eq!(command.arg_count, 2);
+
+    let command = Command::builder()
+        .program("rustup run nightly cargo".to_owned())
+        .executable("rustup".to_owned())
+        .title("nightly".to_owned())
+        .arg_count(0)
+        .build()
+        .unwrap();
+    assert_eq!(command.program, "rustup run nightly cargo");
+    assert_eq!(command.title.as_deref(), Some("nightly"));
+    assert_eq!(command.arg_count, 0);
+
+    let server = Server::builder().port(443).build();
+    assert!(server.tls);
+    assert!(server.explicit_port);
+
+    let server = Server::builder().build();
+    assert!(!server.tls);
+    assert!(!server.explicit_port);
+}
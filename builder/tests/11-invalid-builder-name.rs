@@ -0,0 +1,18 @@
+// The value of #[builder(name = "...")] must be a legal identifier and must
+// not be the name of the struct itself.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(name = "Command Spec")]
+pub struct Command {
+    executable: String,
+}
+
+#[derive(Builder)]
+#[builder(name = "Shell")]
+pub struct Shell {
+    program: String,
+}
+
+fn main() {}
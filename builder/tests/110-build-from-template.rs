@@ -0,0 +1,115 @@
+// #[builder(template)] generates `build_from_template`, which takes the fields
+// which are set from the builder and the other ones from a template instance,
+// so that the builder is used as a patch of the template. No required field is
+// missing, and a collection is taken from the template if it is empty in the
+// builder, even if it has been set to be empty, or appended to the one of the
+// template with #[builder(merge = "append")]. The method is named apart from
+// #[builder(build_with = "...")], which gives the function constructing the
+// struct.
+
+use derive_builder::Builder;
+
+#[derive(Builder, Clone, Debug, PartialEq)]
+#[builder(template)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[builder(each = "env", merge = "append")]
+    env: Vec<String>,
+    current_dir: Option<String>,
+    #[builder(each = "feature")]
+    features: Option<Vec<String>>,
+    #[builder(default = "3")]
+    retries: u8,
+    #[builder(skip)]
+    pid: u32,
+}
+
+#[derive(Builder, Debug)]
+#[builder(pattern = "owned", typestate, template)]
+pub struct Job {
+    name: String,
+    priority: u8,
+}
+
+fn check_port(port: &u16) -> Result<(), String> {
+    if *port == 0 {
+        return Err("port must not be 0".to_owned());
+    }
+    Ok(())
+}
+
+#[derive(Builder, Debug)]
+#[builder(template)]
+pub struct Server {
+    host: String,
+    #[builder(validate = "check_port")]
+    port: u16,
+}
+
+fn main() {
+    let template = Command {
+        executable: "cargo".to_owned(),
+        args: vec!["build".to_owned()],
+        env: vec!["RUST_LOG=info".to_owned()],
+        current_dir: Some("/tmp".to_owned()),
+        features: Some(vec!["tls".to_owned()]),
+        retries: 5,
+        pid: 42,
+    };
+
+    let command = Command::builder().build_from_template(template.clone());
+    assert_eq!(command, template);
+
+    let command = Command::builder()
+        .arg("test".to_owned())
+        .env("RUST_BACKTRACE=1".to_owned())
+        .retries(1)
+        .build_from_template(template.clone());
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["test"]);
+    assert_eq!(command.env, vec!["RUST_LOG=info", "RUST_BACKTRACE=1"]);
+    assert_eq!(command.current_dir.as_deref(), Some("/tmp"));
+    assert_eq!(command.retries, 1);
+    assert_eq!(command.pid, 42);
+
+    // a collection set to be empty is taken from the template as well.
+    let command = Command::builder()
+        .args(Vec::new())
+        .env_all(Vec::new())
+        .features(Vec::new())
+        .build_from_template(template.clone());
+    assert_eq!(command.args, vec!["build"]);
+    assert_eq!(command.env, vec!["RUST_LOG=info"]);
+    assert_eq!(command.features, Some(vec!["tls".to_owned()]));
+
+    let command = Command::builder()
+        .feature("gzip".to_owned())
+        .build_from_template(template.clone());
+    assert_eq!(command.features, Some(vec!["gzip".to_owned()]));
+
+    let job = Job::builder().priority(1).build_from_template(Job {
+        name: "default".to_owned(),
+        priority: 0,
+    });
+    assert_eq!(job.name, "default");
+    assert_eq!(job.priority, 1);
+
+    let template = Server {
+        host: "localhost".to_owned(),
+        port: 8080,
+    };
+    let server = Server::builder()
+        .host("example.com".to_owned())
+        .build_from_template(template)
+        .unwrap();
+    assert_eq!(server.host, "example.com");
+    assert_eq!(server.port, 8080);
+
+    let template = Server {
+        host: "localhost".to_owned(),
+        port: 8080,
+    };
+    assert!(Server::builder().port(0).build_from_template(template).is_err());
+}
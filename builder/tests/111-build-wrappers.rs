@@ -0,0 +1,51 @@
+// #[builder(build_wrappers)] generates `build_arc` and `build_boxed`, which
+// build the struct into `Arc` or `Box` with the same error as `build`. They
+// follow the name of the build function, such as `finish_arc` for `finish`.
+
+use derive_builder::Builder;
+use std::sync::Arc;
+
+#[derive(Builder, Debug)]
+#[builder(build_wrappers)]
+pub struct Command {
+    executable: String,
+    args: Vec<String>,
+}
+
+#[derive(Builder)]
+#[builder(build_wrappers, build_fn = "finish", pattern = "owned")]
+pub struct Options {
+    verbose: Option<bool>,
+}
+
+#[derive(Builder)]
+#[builder(build_wrappers, typestate)]
+pub struct Job {
+    name: String,
+}
+
+fn main() {
+    let command: Arc<Command> = Command::builder()
+        .executable("cargo".to_owned())
+        .build_arc()
+        .unwrap();
+    assert_eq!(command.executable, "cargo");
+
+    let command: Box<Command> = Command::builder()
+        .executable("cargo".to_owned())
+        .args(vec!["build".to_owned()])
+        .build_boxed()
+        .unwrap();
+    assert_eq!(command.args, vec!["build"]);
+
+    let err = Command::builder().build_arc().unwrap_err();
+    assert_eq!(err.to_string(), "field `executable` was not set");
+
+    let options: Arc<Options> = Options::builder().verbose(true).finish_arc();
+    assert_eq!(options.verbose, Some(true));
+    let options: Box<Options> = Options::builder().finish_boxed();
+    assert_eq!(options.verbose, None);
+
+    let job: Box<Job> = Job::builder().name("test".to_owned()).build_boxed();
+    assert_eq!(job.name, "test");
+}
@@ -0,0 +1,62 @@
+// Every public item generated by the opt-in attributes is documented as
+// well, such as `merge`, `build_from_template`, `build_arc`, the `From`
+// conversions and the builder selecting the variant of an enum, so that the
+// crate denying `missing_docs` is able to use any of them.
+
+#![deny(missing_docs)]
+
+//! The crate denying `missing_docs`.
+
+use derive_builder::Builder;
+
+/// The options of the command.
+#[derive(Clone, Debug, Builder)]
+#[builder(fallible, from_self, debug)]
+pub struct Options {
+    level: Option<u8>,
+}
+
+/// The command to run.
+#[derive(Debug, Builder)]
+#[builder(merge, template, build_wrappers, from_self, try_from, debug, panic_helper)]
+pub struct Command {
+    #[builder(ctor_args)]
+    executable: String,
+    #[builder(each = "arg", merge = "append")]
+    args: Vec<String>,
+    #[builder(flag)]
+    verbose: bool,
+    #[builder(sub_builder)]
+    options: Options,
+    #[builder(setter(transform = "|jobs: u8| u32::from(jobs)"))]
+    jobs: Option<u32>,
+}
+
+/// The signal to the process.
+#[derive(Debug, Builder)]
+pub enum Signal {
+    /// Stops the process.
+    Stop {
+        /// The exit code.
+        code: Option<i32>,
+    },
+}
+
+fn main() {
+    let command = Command::builder("cargo".to_owned())
+        .arg("build".to_owned())
+        .verbose()
+        .options(|options| {
+            options.level(1);
+        })
+        .jobs(4)
+        .build_or_panic();
+    assert_eq!(command.jobs, Some(4));
+
+    let mut builder = CommandBuilder::from(command);
+    let command = builder.arg("--release".to_owned()).build_arc().unwrap();
+    assert_eq!(command.args, vec!["build", "--release"]);
+
+    let signal = Signal::builder().stop().code(1).build();
+    assert!(matches!(signal, Signal::Stop { code: Some(1) }));
+}
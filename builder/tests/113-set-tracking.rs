@@ -0,0 +1,81 @@
+// Every field of the builder has a flag telling whether it is set, so that
+// the field set to be `None` or empty is told from the one which is never
+// set. The default is used only if the field is never set, `merge` takes the
+// field set to be `None` or empty while `build_from_template` takes the
+// collection of the template instead of an empty one, and `Debug` prints the
+// collection as it is. `build` clears the flags of the fields it takes, so
+// that `missing_fields` tells them again.
+
+use derive_builder::Builder;
+
+#[derive(Builder, Debug)]
+#[builder(merge, template, debug)]
+pub struct Command {
+    executable: String,
+    #[builder(default = "vec![\"--quiet\".to_owned()]")]
+    flags: Vec<String>,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[builder(keep_option, default = "Some(\"/tmp\".to_owned())")]
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.flags, vec!["--quiet"]);
+    assert!(command.args.is_empty());
+    assert_eq!(command.current_dir, Some("/tmp".to_owned()));
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .flags(Vec::new())
+        .current_dir(None)
+        .build()
+        .unwrap();
+    assert!(command.flags.is_empty());
+    assert_eq!(command.current_dir, None);
+
+    let mut builder = Command::builder();
+    assert_eq!(
+        format!("{:?}", builder),
+        "CommandBuilder { executable: <unset>, flags: [], args: [], current_dir: <unset> }",
+    );
+    builder.arg("build".to_owned()).current_dir(None);
+    assert_eq!(
+        format!("{:?}", builder),
+        "CommandBuilder { executable: <unset>, flags: [], args: [\"build\"], current_dir: None }",
+    );
+    builder.executable("cargo".to_owned());
+    assert!(builder.is_complete());
+    builder.build().unwrap();
+    assert_eq!(builder.missing_fields(), ["executable"]);
+
+    let mut base = Command::builder();
+    base.executable("cargo".to_owned()).arg("build".to_owned());
+    let command = base.merge(Command::builder()).build().unwrap();
+    assert_eq!(command.args, vec!["build"]);
+
+    let mut base = Command::builder();
+    base.executable("cargo".to_owned()).arg("build".to_owned());
+    base.current_dir(Some("/home".to_owned()));
+    let mut cleared = Command::builder();
+    cleared.args(Vec::new()).current_dir(None);
+    let command = base.merge(cleared).build().unwrap();
+    assert!(command.args.is_empty());
+    assert_eq!(command.current_dir, None);
+
+    let template = Command {
+        executable: "cargo".to_owned(),
+        flags: vec!["--verbose".to_owned()],
+        args: vec!["build".to_owned()],
+        current_dir: None,
+    };
+    let command = Command::builder()
+        .flags(Vec::new())
+        .build_from_template(template);
+    assert_eq!(command.flags, vec!["--verbose"]);
+    assert_eq!(command.args, vec!["build"]);
+}
@@ -0,0 +1,47 @@
+// #[builder(each = "...", map)] works for `BTreeMap` as for `HashMap`, whose
+// type arguments are taken as the key and the value. The entries are kept in
+// the order of the keys whichever order they are inserted in.
+
+use derive_builder::Builder;
+use std::collections::BTreeMap;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "env", map)]
+    env: BTreeMap<String, String>,
+    #[builder(each = "limit", map)]
+    limits: Option<std::collections::BTreeMap<&'static str, u64>>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .env("RUST_LOG".to_owned(), "info".to_owned())
+        .env_all(vec![
+            ("CARGO_HOME".to_owned(), "/tmp".to_owned()),
+            ("RUST_LOG".to_owned(), "debug".to_owned()),
+            ("CARGO_TARGET_DIR".to_owned(), "target".to_owned()),
+        ])
+        .limit("threads", 8)
+        .limit("jobs", 4)
+        .build()
+        .unwrap();
+
+    let env: Vec<(&str, &str)> = command
+        .env
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .collect();
+    assert_eq!(
+        env,
+        vec![
+            ("CARGO_HOME", "/tmp"),
+            ("CARGO_TARGET_DIR", "target"),
+            ("RUST_LOG", "debug"),
+        ]
+    );
+
+    let limits: Vec<(&str, u64)> = command.limits.unwrap().into_iter().collect();
+    assert_eq!(limits, vec![("jobs", 4), ("threads", 8)]);
+}
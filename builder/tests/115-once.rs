@@ -0,0 +1,91 @@
+// With `#[builder(once)]` on a field, its setter panics if the field has
+// already been set, instead of silently overwriting the value. With
+// `#[builder(once = "error")]`, the field set twice is recorded and `build`
+// returns `ValidationError` naming it. The fields without the attribute keep
+// the last value set.
+
+use derive_builder::Builder;
+use std::panic;
+
+#[derive(Builder, Debug)]
+pub struct Command {
+    #[builder(once)]
+    executable: String,
+    #[builder(once = "error")]
+    current_dir: Option<String>,
+    #[builder(once = "error")]
+    timeout: u64,
+    env: Option<String>,
+    #[builder(once)]
+    r#type: Option<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .timeout(10)
+        .env("a".to_owned())
+        .env("b".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.env.as_deref(), Some("b"));
+
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(|| {
+        let mut builder = Command::builder();
+        let _ = builder
+            .executable("cargo".to_owned())
+            .executable("rustc".to_owned());
+    });
+    let message = result.unwrap_err();
+    assert_eq!(
+        message.downcast_ref::<&str>(),
+        Some(&"field `executable` was already set")
+    );
+
+    // a raw field is named without `r#`.
+    let result = panic::catch_unwind(|| {
+        let mut builder = Command::builder();
+        let _ = builder
+            .r#type("lib".to_owned())
+            .r#type("bin".to_owned());
+    });
+    let _ = panic::take_hook();
+    let message = result.unwrap_err();
+    assert_eq!(
+        message.downcast_ref::<&str>(),
+        Some(&"field `type` was already set")
+    );
+
+    let err = Command::builder()
+        .executable("cargo".to_owned())
+        .timeout(10)
+        .timeout(20)
+        .current_dir("..".to_owned())
+        .current_dir("/".to_owned())
+        .build()
+        .unwrap_err();
+    assert_eq!(err.fields(), ["timeout"]);
+    assert_eq!(err.to_string(), "field `timeout` is invalid: it was already set");
+
+    let errors = Command::builder()
+        .current_dir("..".to_owned())
+        .current_dir("/".to_owned())
+        .try_build()
+        .unwrap_err();
+    assert_eq!(errors.len(), 3);
+    assert_eq!(errors[0].fields(), ["current_dir"]);
+
+    // `reset` forgets the field set twice.
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .timeout(10)
+        .timeout(20)
+        .reset()
+        .executable("cargo".to_owned())
+        .timeout(30)
+        .build()
+        .unwrap();
+    assert_eq!(command.timeout, 30);
+}
@@ -0,0 +1,38 @@
+// With `#[builder(keep)]` on a field, `build` clones the value of the field
+// instead of taking it out, so that the field is still set in the builder
+// after `build`, while the other fields are taken as usual.
+
+use derive_builder::Builder;
+
+#[derive(Builder, Debug)]
+#[builder(debug)]
+pub struct Command {
+    #[builder(keep)]
+    executable: String,
+    #[builder(keep)]
+    args: Vec<String>,
+    current_dir: String,
+}
+
+fn main() {
+    let mut builder = Command::builder();
+    let command = builder
+        .executable("cargo".to_owned())
+        .args(vec!["build".to_owned()])
+        .current_dir("..".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build"]);
+    assert_eq!(
+        format!("{:?}", builder),
+        r#"CommandBuilder { executable: "cargo", args: ["build"], current_dir: <unset> }"#
+    );
+
+    let err = builder.build().unwrap_err();
+    assert_eq!(err.fields(), ["current_dir"]);
+
+    let command = builder.current_dir("/".to_owned()).build().unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.current_dir, "/");
+}
@@ -0,0 +1,58 @@
+// With `#[builder(mutators)]`, the builder has a `map_{field}` method for each
+// field, which applies a closure to the value set by an earlier stage, such as
+// after `merge`, and does nothing if the field is not set. A collection is
+// passed to the closure by reference.
+
+use derive_builder::Builder;
+use std::time::Duration;
+
+#[derive(Builder, Debug)]
+#[builder(mutators, merge)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    timeout: Option<Duration>,
+    #[builder(rename = "dir")]
+    current_dir: Option<String>,
+}
+
+#[derive(Builder, Debug)]
+#[builder(mutators, pattern = "owned")]
+pub struct Job {
+    name: String,
+}
+
+fn main() {
+    let mut defaults = Command::builder();
+    defaults
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .timeout(Duration::from_secs(10));
+
+    let command = Command::builder()
+        .merge(defaults)
+        .map_timeout(|timeout| timeout * 2)
+        .map_args(|args| args.push("--release".to_owned()))
+        .map_dir(|dir| dir + "/target")
+        .map_executable(|executable| executable.to_uppercase())
+        .build()
+        .unwrap();
+    assert_eq!(command.executable, "CARGO");
+    assert_eq!(command.args, vec!["build", "--release"]);
+    assert_eq!(command.timeout, Some(Duration::from_secs(20)));
+    assert_eq!(command.current_dir, None);
+
+    let err = Command::builder()
+        .map_executable(|_| "rustc".to_owned())
+        .build()
+        .unwrap_err();
+    assert_eq!(err.fields(), ["executable"]);
+
+    let job = Job::builder()
+        .name("test".to_owned())
+        .map_name(|name| name + "s")
+        .build()
+        .unwrap();
+    assert_eq!(job.name, "tests");
+}
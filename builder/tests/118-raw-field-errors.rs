@@ -0,0 +1,38 @@
+// The errors and `missing_fields` name a raw field such as `r#type` without
+// its `r#` prefix, as the docs of the setters do.
+
+use derive_builder::Builder;
+
+#[derive(Builder, Debug)]
+pub struct Target {
+    r#type: String,
+    #[builder(validate = "check_name")]
+    r#mod: String,
+}
+
+fn check_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("must not be empty".to_owned());
+    }
+    Ok(())
+}
+
+fn main() {
+    let builder = Target::builder();
+    assert_eq!(builder.missing_fields(), ["type", "mod"]);
+
+    let err = Target::builder()
+        .r#mod("builder".to_owned())
+        .build()
+        .unwrap_err();
+    assert_eq!(err.fields(), ["type"]);
+    assert_eq!(err.to_string(), "field `type` was not set");
+
+    let err = Target::builder()
+        .r#type("lib".to_owned())
+        .r#mod(String::new())
+        .build()
+        .unwrap_err();
+    assert_eq!(err.fields(), ["mod"]);
+    assert_eq!(err.to_string(), "field `mod` is invalid: must not be empty");
+}
@@ -0,0 +1,54 @@
+// A field named after a method which the builder has otherwise, such as
+// `reset`, `with`, `missing_fields` or `is_complete`, keeps its setter, and
+// the method of the builder is not generated. `build` still tells the
+// missing fields. The other methods are kept, such as `is_complete` of
+// `Command` and `reset` of `Task`.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    reset: bool,
+    with: Option<String>,
+}
+
+#[derive(Builder)]
+pub struct Task {
+    name: String,
+    missing_fields: Vec<String>,
+    is_complete: bool,
+}
+
+fn main() {
+    let mut builder = Command::builder();
+    builder.reset(true).with("cargo".to_owned());
+    assert!(!builder.is_complete());
+    assert!(builder.build().is_err());
+    let command = builder
+        .executable("cargo".to_owned())
+        .reset(true)
+        .with("cargo".to_owned())
+        .build()
+        .unwrap();
+    assert!(command.reset);
+    assert_eq!(command.with, Some("cargo".to_owned()));
+
+    let mut builder = Task::builder();
+    builder
+        .missing_fields(vec!["name".to_owned()])
+        .is_complete(true);
+    match builder.build() {
+        Err(TaskBuilderError::UninitializedFields { names }) => assert_eq!(names, ["name"]),
+        _ => panic!("`name` is missing"),
+    }
+    let task = builder
+        .reset()
+        .name("test".to_owned())
+        .missing_fields(vec!["name".to_owned()])
+        .is_complete(true)
+        .build()
+        .unwrap();
+    assert_eq!(task.missing_fields, ["name"]);
+    assert!(task.is_complete);
+}
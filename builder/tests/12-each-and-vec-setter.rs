@@ -0,0 +1,39 @@
+// When the name given by #[builder(each = "...")] differs from the field name,
+// both setters are generated: the plural one replaces the whole Vec and the
+// singular one appends a single element. Whichever is called later wins, so
+// calling the plural setter after the singular one discards what was pushed.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .arg("--release".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.args, vec!["build", "--release"]);
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .args(vec!["build".to_owned()])
+        .arg("--release".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.args, vec!["build", "--release"]);
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .args(vec!["test".to_owned()])
+        .build()
+        .unwrap();
+    assert_eq!(command.args, vec!["test"]);
+}
@@ -0,0 +1,47 @@
+// Without any attribute, the struct converts back into its builder by `From`,
+// which moves the values and requires nothing of the fields, so that a built
+// value is able to be tweaked and built again. The fields with
+// `#[builder(skip)]` are dropped and built from their defaults again.
+
+use derive_builder::Builder;
+
+// not `Clone`, which `From` does not require.
+#[derive(Debug, PartialEq)]
+pub struct Handle(u32);
+
+#[derive(Builder, Debug)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+    #[builder(default = 30)]
+    timeout: u64,
+    handle: Option<Handle>,
+    #[builder(skip)]
+    pid: u32,
+}
+
+fn main() {
+    let mut command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .handle(Handle(7))
+        .build()
+        .unwrap();
+    command.pid = 42;
+    assert_eq!(command.timeout, 30);
+
+    let mut builder: CommandBuilder = command.into();
+    builder.timeout(5).arg("--release".to_owned());
+    let command = builder.build().unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build", "--release"]);
+    assert_eq!(command.current_dir, None);
+    assert_eq!(command.timeout, 5);
+    assert_eq!(command.handle, Some(Handle(7)));
+    assert_eq!(command.pid, 0);
+
+    let command = CommandBuilder::from(command).build().unwrap();
+    assert_eq!(command.timeout, 5);
+}
@@ -0,0 +1,22 @@
+// The builder of a field with #[builder(sub_builder)] is created on the
+// first call of the setter without any argument, so that the type of the
+// field must not have #[builder(ctor_args)]. The error points at the
+// attribute, since the builder of `ListenerConfig` has no `Default`.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct ListenerConfig {
+    #[builder(ctor_args)]
+    host: String,
+    port: u16,
+}
+
+#[derive(Builder)]
+pub struct Server {
+    #[builder(sub_builder)]
+    listener: ListenerConfig,
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,57 @@
+// The values which `From` and `to_builder` put into the builder do not count
+// as set by the setter for `#[builder(once)]`, so that a built value is able
+// to be tweaked once and built again. Calling the setter twice after that is
+// rejected as usual.
+
+use derive_builder::Builder;
+use std::panic;
+
+#[derive(Builder, Clone, Debug)]
+#[builder(from_self)]
+pub struct Command {
+    #[builder(once)]
+    executable: String,
+    #[builder(once = "error")]
+    timeout: u64,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .timeout(10)
+        .build()
+        .unwrap();
+
+    let mut builder = CommandBuilder::from(command.clone());
+    builder.executable("rustc".to_owned()).timeout(5);
+    let tweaked = builder.build().unwrap();
+    assert_eq!(tweaked.executable, "rustc");
+    assert_eq!(tweaked.timeout, 5);
+
+    let tweaked = command.to_builder().timeout(20).build().unwrap();
+    assert_eq!(tweaked.executable, "cargo");
+    assert_eq!(tweaked.timeout, 20);
+
+    let err = command
+        .to_builder()
+        .timeout(20)
+        .timeout(30)
+        .build()
+        .unwrap_err();
+    assert_eq!(err.fields(), ["timeout"]);
+
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(|| {
+        let mut builder = CommandBuilder::from(command);
+        let _ = builder
+            .executable("rustc".to_owned())
+            .executable("clippy".to_owned());
+    });
+    let _ = panic::take_hook();
+    let message = result.unwrap_err();
+    assert_eq!(
+        message.downcast_ref::<&str>(),
+        Some(&"field `executable` was already set")
+    );
+}
@@ -0,0 +1,103 @@
+// A failed `build` leaves the builder as it was. Every field is checked by
+// its #[builder(validate = "...")] function, #[builder(default_try = ...)] is
+// evaluated and the sub-builders are built before any value is taken out of
+// the builder, so that the field at fault is able to be fixed and the same
+// builder is built again.
+
+use derive_builder::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+pub struct ListenerConfig {
+    host: String,
+    port: u16,
+}
+
+#[derive(Builder, Debug, PartialEq)]
+pub struct Server {
+    name: String,
+    #[builder(validate = "check_workers", default = "4")]
+    workers: u32,
+    #[builder(default_try = std::env::var("DERIVE_BUILDER_TEST_UNSET_HOME"))]
+    home: String,
+    #[builder(sub_builder)]
+    listener: ListenerConfig,
+    #[builder(sub_builder, validate = "check_admin")]
+    admin: ListenerConfig,
+}
+
+fn check_workers(value: &u32) -> Result<(), String> {
+    if *value == 0 {
+        Err("workers must not be zero".to_owned())
+    } else {
+        Ok(())
+    }
+}
+
+fn check_admin(value: &ListenerConfig) -> Result<(), String> {
+    if value.host == "0.0.0.0" {
+        Err("admin must not listen on every address".to_owned())
+    } else {
+        Ok(())
+    }
+}
+
+fn main() {
+    let mut builder = Server::builder();
+    builder
+        .name("api".to_owned())
+        .workers(0)
+        .listener(|listener| {
+            listener.host("localhost".to_owned()).port(8080);
+        })
+        .admin(|admin| {
+            admin.host("0.0.0.0".to_owned());
+        });
+
+    let err = builder.build().unwrap_err();
+    assert_eq!(err.fields(), ["workers"]);
+    builder.workers(8);
+
+    let err = builder.build().unwrap_err();
+    assert_eq!(err.fields(), ["home"]);
+    builder.home("/home/api".to_owned());
+
+    // `listener` is built before `admin` fails and put back.
+    let err = builder.build().unwrap_err();
+    assert_eq!(err.fields(), ["admin"]);
+    assert_eq!(
+        err.to_string(),
+        "field `admin` failed to be initialized: field `port` was not set"
+    );
+    builder.admin(|admin| {
+        admin.port(9090);
+    });
+
+    let err = builder.build().unwrap_err();
+    assert_eq!(err.fields(), ["admin"]);
+    assert_eq!(
+        err.to_string(),
+        "field `admin` is invalid: admin must not listen on every address"
+    );
+    builder.admin(|admin| {
+        admin.host("localhost".to_owned());
+    });
+
+    let server = builder.build().unwrap();
+    assert_eq!(
+        server,
+        Server {
+            name: "api".to_owned(),
+            workers: 8,
+            home: "/home/api".to_owned(),
+            listener: ListenerConfig {
+                host: "localhost".to_owned(),
+                port: 8080,
+            },
+            admin: ListenerConfig {
+                host: "localhost".to_owned(),
+                port: 9090,
+            },
+        }
+    );
+    assert!(!builder.is_complete());
+}
@@ -0,0 +1,74 @@
+// `try_build` checks every field before any value is taken out of the builder
+// as well, so that the builder is left as it was if some error is found. The
+// fields at fault are able to be fixed and the same builder is built again.
+
+use derive_builder::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+pub struct ListenerConfig {
+    host: String,
+    port: u16,
+}
+
+#[derive(Builder, Debug, PartialEq)]
+pub struct Server {
+    #[builder(validate = "check_name")]
+    name: String,
+    #[builder(default_try = std::env::var("DERIVE_BUILDER_TEST_UNSET_HOME"))]
+    home: String,
+    args: Vec<String>,
+    #[builder(sub_builder)]
+    listener: ListenerConfig,
+    #[builder(sub_builder)]
+    admin: ListenerConfig,
+}
+
+fn check_name(value: &String) -> Result<(), String> {
+    if value.is_empty() {
+        Err("name must not be empty".to_owned())
+    } else {
+        Ok(())
+    }
+}
+
+fn main() {
+    let mut builder = Server::builder();
+    builder
+        .name("".to_owned())
+        .args(vec!["--verbose".to_owned()])
+        .listener(|listener| {
+            listener.host("localhost".to_owned()).port(8080);
+        })
+        .admin(|admin| {
+            admin.host("localhost".to_owned());
+        });
+
+    let errors = builder.try_build().unwrap_err();
+    let fields: Vec<_> = errors.iter().map(|err| err.fields()).collect();
+    assert_eq!(fields, [["name"], ["home"], ["admin"]]);
+
+    builder
+        .name("api".to_owned())
+        .home("/home/api".to_owned())
+        .admin(|admin| {
+            admin.port(9090);
+        });
+    let server = builder.try_build().unwrap();
+    assert_eq!(
+        server,
+        Server {
+            name: "api".to_owned(),
+            home: "/home/api".to_owned(),
+            args: vec!["--verbose".to_owned()],
+            listener: ListenerConfig {
+                host: "localhost".to_owned(),
+                port: 8080,
+            },
+            admin: ListenerConfig {
+                host: "localhost".to_owned(),
+                port: 9090,
+            },
+        }
+    );
+    assert!(!builder.is_complete());
+}
@@ -0,0 +1,51 @@
+// The struct-level attribute #[builder(setter(into))] makes every setter take
+// `impl Into<T>`, so that String or PathBuf fields can be set from a &str. A
+// field may still opt out with #[builder(into = false)], or opt in on its own
+// with #[builder(into)] when the struct-level attribute is absent.
+
+use derive_builder::Builder;
+use std::path::PathBuf;
+
+#[derive(Builder)]
+#[builder(setter(into))]
+pub struct Command {
+    executable: String,
+    program: PathBuf,
+    current_dir: Option<PathBuf>,
+    #[builder(into = false)]
+    timeout: u64,
+}
+
+mod shell {
+    use derive_builder::Builder;
+
+    #[derive(Builder)]
+    pub struct Shell {
+        #[builder(into)]
+        pub program: String,
+        pub login: bool,
+    }
+
+    pub fn bash() -> Shell {
+        Shell::builder().program("bash").login(true).build().unwrap()
+    }
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo")
+        .program("/usr/bin/cargo")
+        .current_dir("/tmp")
+        .timeout(30)
+        .build()
+        .unwrap();
+
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.program, PathBuf::from("/usr/bin/cargo"));
+    assert_eq!(command.current_dir, Some(PathBuf::from("/tmp")));
+    assert_eq!(command.timeout, 30);
+
+    let shell = shell::bash();
+    assert_eq!(shell.program, "bash");
+    assert!(shell.login);
+}
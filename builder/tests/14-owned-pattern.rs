@@ -0,0 +1,65 @@
+// The struct-level attribute #[builder(pattern = "owned")] makes the setters
+// take and return the builder by value and makes `build` consume the builder.
+// This allows a partially configured builder to be returned from a function
+// in the middle of a chain. The default pattern, which can also be spelled
+// #[builder(pattern = "mutable")], keeps the `&mut self` setters.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(pattern = "owned")]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+}
+
+fn cargo() -> CommandBuilder {
+    Command::builder().executable("cargo".to_owned())
+}
+
+mod mutable {
+    use derive_builder::Builder;
+
+    #[derive(Builder)]
+    #[builder(pattern = "mutable")]
+    pub struct Command {
+        pub executable: String,
+        pub args: Vec<String>,
+    }
+
+    pub fn cargo_build() -> Command {
+        Command::builder()
+            .executable("cargo".to_owned())
+            .args(vec!["build".to_owned()])
+            .build()
+            .unwrap()
+    }
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .args(vec!["build".to_owned()])
+        .build()
+        .unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build"]);
+    assert!(command.current_dir.is_none());
+
+    let command = cargo()
+        .arg("test".to_owned())
+        .current_dir("..".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["test"]);
+    assert_eq!(command.current_dir.unwrap(), "..");
+
+    assert!(Command::builder().build().is_err());
+
+    let command = mutable::cargo_build();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build"]);
+}
@@ -0,0 +1,11 @@
+// Only "mutable" and "owned" are accepted as the builder pattern.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(pattern = "immutable")]
+pub struct Command {
+    executable: String,
+}
+
+fn main() {}
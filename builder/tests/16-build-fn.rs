@@ -0,0 +1,44 @@
+// The build function is `pub fn build` by default. The struct-level attribute
+// #[builder(build_fn = "...")] renames it, which is needed when the origin
+// type already has an inherent method of the same name, and the list form
+// #[builder(build_fn(name = "...", vis = "..."))] also sets its visibility.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(build_fn = "finish")]
+pub struct Command {
+    executable: String,
+}
+
+impl Command {
+    pub fn build(&self) -> String {
+        format!("{} build", self.executable)
+    }
+}
+
+mod shell {
+    use derive_builder::Builder;
+
+    #[derive(Builder)]
+    #[builder(build_fn(name = "spawn", vis = "pub(crate)"))]
+    pub struct Shell {
+        pub program: String,
+    }
+
+    pub mod bash {
+        pub fn shell() -> super::Shell {
+            super::Shell::builder().program("bash".to_owned()).spawn().unwrap()
+        }
+    }
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .finish()
+        .unwrap();
+    assert_eq!(command.build(), "cargo build");
+
+    assert_eq!(shell::bash::shell().program, "bash");
+}
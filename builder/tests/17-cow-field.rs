@@ -0,0 +1,25 @@
+// The generics of the struct, including lifetimes, are carried over to the
+// builder, so fields such as `Cow<'a, str>` are supported. The setter of a Cow
+// field takes `impl Into<Cow<'a, str>>` so that it is able to be set from both
+// a borrowed and an owned string.
+
+use derive_builder::Builder;
+use std::borrow::Cow;
+
+#[derive(Builder)]
+pub struct Command<'a> {
+    executable: Cow<'a, str>,
+    current_dir: Option<Cow<'a, str>>,
+}
+
+fn main() {
+    let executable = String::from("cargo");
+    let command = Command::builder()
+        .executable(executable.as_str())
+        .current_dir(String::from(".."))
+        .build()
+        .unwrap();
+
+    assert!(matches!(command.executable, Cow::Borrowed("cargo")));
+    assert!(matches!(command.current_dir, Some(Cow::Owned(ref dir)) if dir == ".."));
+}
@@ -0,0 +1,38 @@
+// The struct-level attribute #[builder(module = "...")] emits the builder and
+// its `BuildError` inside a module of the given name and re-exports only the
+// builder, so that several structs in one scope do not collide with each other.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(module = "command_builder")]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+}
+
+#[derive(Builder)]
+#[builder(module = "shell_builder")]
+pub struct Shell {
+    program: String,
+    login: Option<bool>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build"]);
+
+    let mut shell: ShellBuilder = Shell::builder();
+    let result: Result<Shell, shell_builder::BuildError> = shell.build();
+    assert!(result.is_err());
+
+    let shell = Shell::builder().program("bash".to_owned()).build().unwrap();
+    assert_eq!(shell.program, "bash");
+    assert!(shell.login.is_none());
+}
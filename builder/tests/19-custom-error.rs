@@ -0,0 +1,47 @@
+// The struct-level attribute #[builder(error = "...")] makes `build` return
+// the given error type instead of the generated `CommandBuilderError`, so that
+// the result is able to be propagated with `?` in the caller's code. The error
+// type must provide `fn missing_field(field: &'static str) -> Self`.
+
+use derive_builder::Builder;
+
+#[derive(Debug, PartialEq)]
+pub enum ConfigError {
+    MissingField(&'static str),
+    Invalid(String),
+}
+
+impl ConfigError {
+    pub fn missing_field(field: &'static str) -> Self {
+        ConfigError::MissingField(field)
+    }
+}
+
+#[derive(Builder)]
+#[builder(error = "crate::ConfigError")]
+pub struct Command {
+    executable: String,
+    current_dir: Option<String>,
+}
+
+fn cargo(dir: &str) -> Result<Command, ConfigError> {
+    if dir.is_empty() {
+        return Err(ConfigError::Invalid(dir.to_owned()));
+    }
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .current_dir(dir.to_owned())
+        .build()?;
+    Ok(command)
+}
+
+fn main() {
+    let command = cargo("..").unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.current_dir.unwrap(), "..");
+
+    assert_eq!(
+        Command::builder().build().err(),
+        Some(ConfigError::MissingField("executable")),
+    );
+}
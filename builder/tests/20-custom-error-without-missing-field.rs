@@ -0,0 +1,16 @@
+// The error type given by #[builder(error = "...")] must provide
+// `fn missing_field(field: &'static str) -> Self`, otherwise the error points
+// at the attribute.
+
+use derive_builder::Builder;
+
+#[derive(Debug)]
+pub struct ConfigError;
+
+#[derive(Builder)]
+#[builder(error = "ConfigError")]
+pub struct Command {
+    executable: String,
+}
+
+fn main() {}
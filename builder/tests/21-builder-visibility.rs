@@ -0,0 +1,42 @@
+// The builder and `builder()` have the same visibility as the struct, so that
+// a builder of a struct defined in another module is able to be named and
+// used. The struct-level attribute #[builder(vis = "...")] overrides it.
+
+mod command {
+    use derive_builder::Builder;
+
+    #[derive(Builder)]
+    pub struct Command {
+        pub executable: String,
+        pub current_dir: Option<String>,
+    }
+
+    pub mod shell {
+        use derive_builder::Builder;
+
+        #[derive(Builder)]
+        #[builder(vis = "pub(crate)")]
+        pub struct Shell {
+            pub program: String,
+        }
+    }
+}
+
+use command::shell::{Shell, ShellBuilder};
+use command::{Command, CommandBuilder};
+
+fn cargo() -> CommandBuilder {
+    let mut builder = Command::builder();
+    builder.executable("cargo".to_owned());
+    builder
+}
+
+fn main() {
+    let command = cargo().current_dir("..".to_owned()).build().unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.current_dir.unwrap(), "..");
+
+    let mut builder: ShellBuilder = Shell::builder();
+    let shell = builder.program("bash".to_owned()).build().unwrap();
+    assert_eq!(shell.program, "bash");
+}
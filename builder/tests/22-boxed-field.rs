@@ -0,0 +1,35 @@
+// Fields of type `Box<dyn Trait>` are able to be set with a boxed value as is.
+// With #[builder(boxed)], the setter takes `impl Trait + 'static` instead and
+// boxes it, so that a closure is able to be passed directly.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(boxed)]
+    on_exit: Box<dyn Fn(i32) -> bool>,
+    #[builder(boxed)]
+    on_output: Option<Box<dyn FnMut(&str) + Send>>,
+    formatter: Box<dyn Fn(&str) -> String>,
+    #[builder(boxed)]
+    timeout: Box<u64>,
+}
+
+fn main() {
+    let mut command = Command::builder()
+        .executable("cargo".to_owned())
+        .on_exit(|code| code == 0)
+        .on_output(|line: &str| println!("{}", line))
+        .formatter(Box::new(|line| format!("> {}", line)))
+        .timeout(30)
+        .build()
+        .unwrap();
+
+    assert!((command.on_exit)(0));
+    assert!(!(command.on_exit)(1));
+    (command.on_output.as_mut().unwrap())("Compiling");
+    assert_eq!((command.formatter)("Compiling"), "> Compiling");
+    assert_eq!(*command.timeout, 30);
+    assert_eq!(command.executable, "cargo");
+}
@@ -0,0 +1,11 @@
+// #[builder(boxed)] is only meaningful on a field of type `Box<T>`.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(boxed)]
+    executable: String,
+}
+
+fn main() {}
@@ -0,0 +1,46 @@
+// `builder()`, `new()`, the setters and `build()` all share the visibility of
+// the builder, so that a builder derived in a child module is able to be
+// created, filled and built from the parent module.
+
+mod inner {
+    use derive_builder::Builder;
+
+    #[derive(Builder)]
+    pub struct Command {
+        pub executable: String,
+        #[builder(each = "arg")]
+        pub args: Vec<String>,
+        pub current_dir: Option<String>,
+    }
+
+    pub mod owned {
+        use derive_builder::Builder;
+
+        #[derive(Builder)]
+        #[builder(pattern = "owned", module = "shell_builder")]
+        pub struct Shell {
+            pub program: String,
+        }
+    }
+}
+
+fn main() {
+    let command = inner::Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .current_dir("..".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build"]);
+    assert_eq!(command.current_dir.unwrap(), "..");
+
+    let mut builder = inner::CommandBuilder::new();
+    assert!(builder.build().is_err());
+
+    let shell = inner::owned::ShellBuilder::new()
+        .program("bash".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(shell.program, "bash");
+}
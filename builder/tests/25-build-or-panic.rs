@@ -0,0 +1,29 @@
+// The struct-level attribute #[builder(panic_helper)] generates
+// `build_or_panic()`, which unwraps the result of `build()` with a message
+// naming the type being built.
+
+use derive_builder::Builder;
+use std::panic;
+
+#[derive(Builder)]
+#[builder(panic_helper)]
+pub struct Command {
+    executable: String,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .build_or_panic();
+    assert_eq!(command.executable, "cargo");
+    assert!(command.current_dir.is_none());
+
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(|| Command::builder().build_or_panic());
+    let _ = panic::take_hook();
+
+    let message = result.err().unwrap();
+    let message = message.downcast_ref::<String>().unwrap();
+    assert!(message.starts_with("failed to build `Command`"));
+}
@@ -0,0 +1,38 @@
+// #[builder(each = "...")] is also able to be set on a field of type
+// `Option<Vec<T>>`, which distinguishes "no list provided" from "empty list".
+// The field stays `None` unless an element is pushed or the whole list is set.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "env")]
+    envs: Option<Vec<String>>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .build()
+        .unwrap();
+    assert!(command.envs.is_none());
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .env("RUST_LOG=debug".to_owned())
+        .env("RUST_BACKTRACE=1".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(
+        command.envs,
+        Some(vec!["RUST_LOG=debug".to_owned(), "RUST_BACKTRACE=1".to_owned()]),
+    );
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .envs(Vec::new())
+        .build()
+        .unwrap();
+    assert_eq!(command.envs, Some(Vec::new()));
+}
@@ -0,0 +1,51 @@
+// The struct-level attributes #[builder(prefix = "...")] and
+// #[builder(suffix = "...")] are added around the name of every setter, after
+// the field-level #[builder(rename = "...")] is applied. The methods generated
+// by #[builder(each = "...")] get #[builder(each_prefix = "...")] instead.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(prefix = "with_", each_prefix = "push_")]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[builder(rename = "cwd")]
+    current_dir: Option<String>,
+}
+
+mod shell {
+    use derive_builder::Builder;
+
+    #[derive(Builder)]
+    #[builder(suffix = "_is")]
+    pub struct Shell {
+        pub program: String,
+    }
+
+    pub fn bash() -> Shell {
+        Shell::builder().program_is("bash".to_owned()).build().unwrap()
+    }
+}
+
+fn main() {
+    let command = Command::builder()
+        .with_executable("cargo".to_owned())
+        .push_arg("build".to_owned())
+        .with_cwd("..".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build"]);
+    assert_eq!(command.current_dir.unwrap(), "..");
+
+    let command = Command::builder()
+        .with_executable("cargo".to_owned())
+        .with_args(vec!["test".to_owned()])
+        .build()
+        .unwrap();
+    assert_eq!(command.args, vec!["test"]);
+
+    assert_eq!(shell::bash().program, "bash");
+}
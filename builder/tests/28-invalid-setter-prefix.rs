@@ -0,0 +1,11 @@
+// The prefix must produce legal identifiers when prepended to the setters.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(prefix = "with-")]
+pub struct Command {
+    executable: String,
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+// Inside the module given by #[builder(module = "...")], the builder is named
+// just `Builder` and `builder()` returns `command_builder::Builder`. The module
+// has the visibility of the builder, so that the error type is able to be
+// named from other modules as `command_builder::BuildError`.
+
+mod command {
+    use derive_builder::Builder;
+
+    #[derive(Builder)]
+    #[builder(module = "command_builder")]
+    pub struct Command {
+        pub executable: String,
+    }
+}
+
+use command::command_builder::{BuildError, Builder};
+use command::Command;
+
+fn build(mut builder: Builder) -> Result<Command, BuildError> {
+    builder.build()
+}
+
+fn main() {
+    let mut builder: command::command_builder::Builder = Command::builder();
+    builder.executable("cargo".to_owned());
+    assert_eq!(build(builder).unwrap().executable, "cargo");
+
+    let builder: command::CommandBuilder = Command::builder();
+    assert!(build(builder).is_err());
+}
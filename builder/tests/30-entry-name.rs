@@ -0,0 +1,23 @@
+// The struct-level attribute #[builder(entry = "...")] renames the generated
+// `builder()` function, which is needed when the struct already has an
+// inherent method of that name.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(entry = "config")]
+pub struct Command {
+    executable: String,
+}
+
+impl Command {
+    pub fn builder(&self) -> String {
+        format!("{} build", self.executable)
+    }
+}
+
+fn main() {
+    let mut builder: CommandBuilder = Command::config();
+    let command = builder.executable("cargo".to_owned()).build().unwrap();
+    assert_eq!(command.builder(), "cargo build");
+}
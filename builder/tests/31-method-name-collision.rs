@@ -0,0 +1,36 @@
+// Every method of the builder must have a distinct name. Two fields whose
+// #[builder(each = "...")] names are the same, or an `each` name which is the
+// same as the setter of another field, are reported at the offending name.
+// The bulk method `{field}_all` of an `each` field is checked as well.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[builder(each = "arg")]
+    extra_args: Vec<String>,
+}
+
+#[derive(Builder)]
+pub struct Shell {
+    program: String,
+    #[builder(each = "program")]
+    programs: Vec<String>,
+}
+
+#[derive(Builder)]
+pub struct Cargo {
+    #[builder(rename = "build")]
+    target: String,
+}
+
+#[derive(Builder)]
+pub struct Task {
+    args_all: bool,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+// The struct-level attribute #[builder(typestate)] tracks which required
+// fields are set in the type of the builder, so that `build()` is only able
+// to be called once every required field is set and never fails. Optional and
+// repeated fields do not participate.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(typestate)]
+pub struct Command<'a> {
+    executable: String,
+    program: &'a str,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let command: Command = Command::builder()
+        .arg("build".to_owned())
+        .program("/usr/bin/cargo")
+        .current_dir("..".to_owned())
+        .executable("cargo".to_owned())
+        .arg("--release".to_owned())
+        .build();
+
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.program, "/usr/bin/cargo");
+    assert_eq!(command.args, vec!["build", "--release"]);
+    assert_eq!(command.current_dir.unwrap(), "..");
+
+    let builder = Command::builder().executable("cargo".to_owned());
+    let command = builder.program("cargo").executable("rustc".to_owned()).build();
+    assert_eq!(command.executable, "rustc");
+}
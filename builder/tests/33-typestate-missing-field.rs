@@ -0,0 +1,19 @@
+// Forgetting a required field of a #[builder(typestate)] builder is a compile
+// error instead of a runtime `CommandBuilderError`.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(typestate)]
+pub struct Command {
+    executable: String,
+    program: String,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let _command = Command::builder()
+        .executable("cargo".to_owned())
+        .current_dir("..".to_owned())
+        .build();
+}
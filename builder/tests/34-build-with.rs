@@ -0,0 +1,54 @@
+// The struct-level attribute #[builder(build_with = "...")] makes `build` call
+// the given function with the values of the fields in declaration order,
+// instead of a struct literal, so that a constructor is able to keep the
+// invariants of the struct.
+
+mod command {
+    use derive_builder::Builder;
+
+    #[derive(Builder)]
+    #[builder(build_with = "Command::new_unchecked")]
+    pub struct Command {
+        executable: String,
+        argv: Vec<String>,
+        current_dir: Option<String>,
+    }
+
+    impl Command {
+        /// `argv[0]` is always the executable.
+        pub fn new_unchecked(
+            executable: String,
+            mut argv: Vec<String>,
+            current_dir: Option<String>,
+        ) -> Self {
+            argv.insert(0, executable.clone());
+            Command {
+                executable,
+                argv,
+                current_dir,
+            }
+        }
+
+        pub fn argv(&self) -> &[String] {
+            &self.argv
+        }
+
+        pub fn current_dir(&self) -> Option<&str> {
+            self.current_dir.as_deref()
+        }
+    }
+}
+
+use command::Command;
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .argv(vec!["build".to_owned()])
+        .build()
+        .unwrap();
+    assert_eq!(command.argv(), ["cargo", "build"]);
+    assert_eq!(command.current_dir(), None);
+
+    assert!(Command::builder().build().is_err());
+}
@@ -0,0 +1,19 @@
+// The derive only supports structs and enums whose variants all have named
+// fields. A variant without named fields is reported at its name, and a union
+// at the name of the type, instead of panicking.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub enum Command {
+    Build,
+    Test,
+}
+
+#[derive(Builder)]
+pub union Bits {
+    signed: i32,
+    unsigned: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+// The derive requires named fields, because the setters are named after them.
+// A tuple struct is reported at its fields with a single error.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command(String, Vec<String>);
+
+fn main() {}
@@ -0,0 +1,30 @@
+// A field whose type is a bare type parameter of the struct, such as `value: T`,
+// is a required field. Its setter takes `T`, or `impl Into<T>` with
+// #[builder(into)], and `build` fails if it has not been set.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Wrapper<T> {
+    #[builder(into)]
+    value: T,
+    fallback: Option<T>,
+    #[builder(each = "item")]
+    items: Vec<T>,
+}
+
+fn main() {
+    let wrapper: Wrapper<String> = Wrapper::builder()
+        .value("hello")
+        .fallback("world".to_owned())
+        .item("a".to_owned())
+        .item("b".to_owned())
+        .build()
+        .unwrap();
+
+    assert_eq!(wrapper.value, "hello");
+    assert_eq!(wrapper.fallback.as_deref(), Some("world"));
+    assert_eq!(wrapper.items, vec!["a", "b"]);
+
+    assert!(Wrapper::<String>::builder().build().is_err());
+}
@@ -0,0 +1,33 @@
+// A field of type `Option<T>` is optional by default. With #[builder(required)]
+// it must be set explicitly, and its setter takes the whole `Option<T>` so that
+// `None` is also a value which is able to be set.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(required)]
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .current_dir(None)
+        .build()
+        .unwrap();
+    assert_eq!(command.current_dir, None);
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .current_dir(Some("..".to_owned()))
+        .build()
+        .unwrap();
+    assert_eq!(command.current_dir.as_deref(), Some(".."));
+
+    let result = Command::builder()
+        .executable("cargo".to_owned())
+        .build();
+    assert!(result.is_err());
+}
@@ -0,0 +1,22 @@
+// The value of #[builder(each = "...")] becomes the name of a method, so it
+// must be a legal identifier. A keyword is able to be used as a raw identifier,
+// except `self`, `Self`, `super` and `crate`.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "push-arg")]
+    args: Vec<String>,
+    #[builder(each = "")]
+    env: Vec<String>,
+    #[builder(each = "type")]
+    types: Vec<String>,
+    #[builder(each = "async")]
+    tasks: Vec<String>,
+    #[builder(each = "self")]
+    selves: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+// A keyword is able to be the name of an `each` method by writing it as a raw
+// identifier, also together with #[builder(each_prefix = "...")].
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Task {
+    #[builder(each = "r#type")]
+    types: Vec<String>,
+    #[builder(each = "r#async")]
+    jobs: Vec<u32>,
+}
+
+mod prefixed {
+    use derive_builder::Builder;
+
+    #[derive(Builder)]
+    #[builder(each_prefix = "add_")]
+    pub struct Task {
+        #[builder(each = "r#type")]
+        pub types: Vec<String>,
+    }
+}
+
+fn main() {
+    let task = Task::builder()
+        .r#type("unit".to_owned())
+        .r#type("doc".to_owned())
+        .r#async(1)
+        .build();
+    assert_eq!(task.types, vec!["unit", "doc"]);
+    assert_eq!(task.jobs, vec![1]);
+
+    let task = prefixed::Task::builder()
+        .add_type("unit".to_owned())
+        .build();
+    assert_eq!(task.types, vec!["unit"]);
+}
@@ -0,0 +1,35 @@
+// A field with #[cfg(...)] only exists under the configuration, so the field of
+// the builder, its setter and its part of `build` carry the same attribute.
+//
+// `cfg(all())` is always enabled and `cfg(any())` is always disabled, which
+// stand for a feature turned on and off.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[cfg(all())]
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[cfg(any())]
+    #[builder(each = "env")]
+    env: Vec<String>,
+    #[cfg(all())]
+    current_dir: Option<String>,
+    #[cfg(any())]
+    timeout: u64,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .current_dir("..".to_owned())
+        .build()
+        .unwrap();
+
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build"]);
+    assert_eq!(command.current_dir.as_deref(), Some(".."));
+}
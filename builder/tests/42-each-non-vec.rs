@@ -0,0 +1,19 @@
+// #[builder(each = "...")] is only meaningful on a `Vec` or `Option<Vec>`
+// field. On the other field the error points at the type of the field, and the
+// rest of the builder is still generated so that its uses compile.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(each = "executable")]
+    executable: String,
+    args: Vec<String>,
+}
+
+fn main() {
+    let _ = Command::builder()
+        .executable("cargo".to_owned())
+        .args(vec!["build".to_owned()])
+        .build();
+}
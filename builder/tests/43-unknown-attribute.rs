@@ -0,0 +1,19 @@
+// A misspelled or unsupported key of #[builder(...)] is reported with its name
+// and the supported key it is close to, if any, both on the struct and on a
+// field, instead of being silently ignored.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(nmae = "CommandConfig")]
+pub struct Command {
+    executable: String,
+}
+
+#[derive(Builder)]
+pub struct Shell {
+    #[builder(hidden)]
+    program: String,
+}
+
+fn main() {}
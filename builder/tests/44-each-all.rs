@@ -0,0 +1,28 @@
+// Besides the method pushing one item, #[builder(each = "...")] generates
+// `{field}_all` which extends the `Vec` by the items of an iterator. Both are
+// able to be mixed, and the items are kept in the order they are given.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[builder(each = "env")]
+    env: Option<Vec<String>>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .args_all(vec!["--release".to_owned(), "--quiet".to_owned()])
+        .arg("--locked".to_owned())
+        .env_all(std::iter::once("RUST_LOG=info".to_owned()))
+        .build()
+        .unwrap();
+
+    assert_eq!(command.args, vec!["build", "--release", "--quiet", "--locked"]);
+    assert_eq!(command.env, Some(vec!["RUST_LOG=info".to_owned()]));
+}
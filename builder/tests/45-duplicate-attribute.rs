@@ -0,0 +1,16 @@
+// A key of #[builder(...)] must not be given twice to the same field, even in
+// separate attributes, and conflicting keys must not be combined. The error
+// points at the latter occurrence.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(each = "arg")]
+    #[builder(each = "argument")]
+    args: Vec<String>,
+    #[builder(boxed, into)]
+    program: Box<String>,
+}
+
+fn main() {}
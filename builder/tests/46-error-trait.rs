@@ -0,0 +1,33 @@
+// The generated CommandBuilderError implements Display and std::error::Error,
+// so that `build()?` works in a function returning Result<_, Box<dyn Error>>.
+// It tells the names of the fields which are not set.
+
+use derive_builder::Builder;
+use std::error::Error;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    current_dir: Option<String>,
+}
+
+fn make(executable: Option<&str>) -> Result<Command, Box<dyn Error>> {
+    let mut builder = Command::builder();
+    if let Some(executable) = executable {
+        builder.executable(executable.to_owned());
+    }
+    let command = builder.build()?;
+    Ok(command)
+}
+
+fn main() {
+    let command = make(Some("cargo")).unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.current_dir, None);
+
+    let err = make(None).err().unwrap();
+    assert_eq!(err.to_string(), "field `executable` was not set");
+
+    let err = Command::builder().build().err().unwrap();
+    assert_eq!(err.fields(), ["executable"]);
+}
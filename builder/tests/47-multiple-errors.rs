@@ -0,0 +1,26 @@
+// Every wrong field attribute is reported in a single compile instead of only
+// the first one. The builder is still generated ignoring the wrong attributes,
+// so that the code using it does not add errors of its own.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(eahc = "exe", into)]
+    executable: String,
+    #[builder(each = "push-arg")]
+    args: Vec<String>,
+    #[builder(each = "env")]
+    env: String,
+    #[builder(boxed, defualt)]
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let _ = Command::builder()
+        .executable("cargo")
+        .args(vec![])
+        .env("RUST_LOG=info".to_owned())
+        .current_dir("..".to_owned())
+        .build();
+}
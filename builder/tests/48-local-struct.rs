@@ -0,0 +1,24 @@
+// The derive is able to be used on a struct defined inside a function body,
+// whose builder and CommandBuilderError are defined in the same function.
+
+use derive_builder::Builder;
+
+fn main() {
+    #[derive(Builder)]
+    pub struct Command {
+        executable: String,
+        #[builder(each = "arg")]
+        args: Vec<String>,
+        current_dir: Option<String>,
+    }
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .build()
+        .unwrap();
+
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build"]);
+    assert_eq!(command.current_dir, None);
+}
@@ -0,0 +1,24 @@
+// The name of the setter is able to be given in the nested form
+// #[builder(setter(name = "..."))], which is the same as
+// #[builder(rename = "...")].
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(setter(name = "program"))]
+    executable: String,
+    #[builder(setter(name = "r#in"))]
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .program("cargo".to_owned())
+        .r#in("..".to_owned())
+        .build()
+        .unwrap();
+
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.current_dir.as_deref(), Some(".."));
+}
@@ -0,0 +1,14 @@
+// An unknown key in #[builder(setter(...))] is reported with the supported
+// ones, and the nested name must not be combined with `rename`.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(setter(nmae = "program"))]
+    executable: String,
+    #[builder(rename = "dir", setter(name = "directory"))]
+    current_dir: Option<String>,
+}
+
+fn main() {}
@@ -0,0 +1,50 @@
+// #[builder(each = "...", via = "...")] supports a collection other than `Vec`
+// which adds an item by a method other than `push`. The collection must have
+// `new()` creating an empty one, and the type of the items is taken from its
+// first type argument.
+
+use derive_builder::Builder;
+
+pub struct Bag<T> {
+    items: Vec<T>,
+}
+
+impl<T> Bag<T> {
+    pub fn new() -> Self {
+        Bag { items: Vec::new() }
+    }
+
+    pub fn add(&mut self, item: T) {
+        self.items.insert(0, item);
+    }
+}
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg", via = "add")]
+    args: Bag<String>,
+    #[builder(each = "env", via = "add")]
+    env: Option<Bag<String>>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .args_all(vec!["--release".to_owned(), "--quiet".to_owned()])
+        .build()
+        .unwrap();
+
+    assert_eq!(command.args.items, vec!["--quiet", "--release", "build"]);
+    assert!(command.env.is_none());
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .env("RUST_LOG=info".to_owned())
+        .build()
+        .unwrap();
+
+    assert!(command.args.items.is_empty());
+    assert_eq!(command.env.unwrap().items, vec!["RUST_LOG=info"]);
+}
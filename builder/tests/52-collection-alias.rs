@@ -0,0 +1,39 @@
+// A proc-macro only sees the tokens of the field type, so a type alias of
+// `Vec<T>` is not recognized as a `Vec`. #[builder(collection = "...")] tells
+// the type which the alias stands for, so that the field works with `each` and
+// starts empty instead of being required.
+
+use derive_builder::Builder;
+
+type Args = Vec<String>;
+type Env = Option<Vec<(String, String)>>;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg", collection = "Vec<String>")]
+    args: Args,
+    #[builder(each = "env", collection = "Option<Vec<(String, String)>>")]
+    env: Env,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .arg("--release".to_owned())
+        .build()
+        .unwrap();
+
+    assert_eq!(command.args, vec!["build", "--release"]);
+    assert_eq!(command.env, None);
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .env(("RUST_LOG".to_owned(), "info".to_owned()))
+        .build()
+        .unwrap();
+
+    assert!(command.args.is_empty());
+    assert_eq!(command.env.unwrap().len(), 1);
+}
@@ -0,0 +1,17 @@
+// A builder attribute must be written as #[builder(...)]. The other shapes,
+// such as a bare #[builder] or #[builder = "..."], are reported at the
+// attribute together with the other errors instead of panicking.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder]
+    executable: String,
+    #[builder = "each = arg"]
+    args: Vec<String>,
+    #[builder(each = )]
+    env: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,62 @@
+// #[builder(each = "...", map)] supports a map, which is any type with `new()`
+// and `insert(key, value)` such as `HashMap` or `indexmap::IndexMap`. The
+// method takes a key and a value, and `{field}_all` takes the pairs.
+
+use derive_builder::Builder;
+use std::collections::HashMap;
+
+pub struct OrderedMap<K, V> {
+    entries: Vec<(K, V)>,
+}
+
+impl<K: PartialEq, V> OrderedMap<K, V> {
+    pub fn new() -> Self {
+        OrderedMap {
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        self.entries.retain(|(k, _)| *k != key);
+        self.entries.push((key, value));
+    }
+}
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "env", map)]
+    env: OrderedMap<String, String>,
+    #[builder(each = "limit", map)]
+    limits: Option<HashMap<&'static str, u64>>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .env("RUST_LOG".to_owned(), "info".to_owned())
+        .env_all(vec![
+            ("CARGO_HOME".to_owned(), "/tmp".to_owned()),
+            ("RUST_LOG".to_owned(), "debug".to_owned()),
+        ])
+        .build()
+        .unwrap();
+
+    let env: Vec<(&str, &str)> = command
+        .env
+        .entries
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .collect();
+    assert_eq!(env, vec![("CARGO_HOME", "/tmp"), ("RUST_LOG", "debug")]);
+    assert!(command.limits.is_none());
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .limit("jobs", 4)
+        .build()
+        .unwrap();
+
+    assert!(command.env.entries.is_empty());
+    assert_eq!(command.limits.unwrap()["jobs"], 4);
+}
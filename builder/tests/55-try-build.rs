@@ -0,0 +1,51 @@
+// `try_build` does not stop at the first problem. It returns every required
+// field which is not set and every field rejected by its
+// #[builder(validate = "...")] function, such as
+//
+//     fn check_executable(value: &String) -> Result<(), String>
+//
+// while `build` returns the first one.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(validate = "check_executable")]
+    executable: String,
+    args: Vec<String>,
+    #[builder(validate = "check_current_dir")]
+    current_dir: Option<String>,
+}
+
+fn check_executable(value: &String) -> Result<(), String> {
+    if value.is_empty() {
+        Err("executable must not be empty".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+fn check_current_dir(value: &Option<String>) -> Result<(), String> {
+    match value {
+        Some(dir) if !dir.starts_with('/') => Err(format!("`{}` is not absolute", dir)),
+        _ => Ok(()),
+    }
+}
+
+fn main() {
+    let mut builder = Command::builder();
+    builder.current_dir("..".to_owned());
+    let errors = builder.try_build().err().unwrap();
+    assert_eq!(errors.len(), 2);
+
+    let mut builder = Command::builder();
+    builder.executable("".to_owned());
+    assert!(builder.build().is_err());
+
+    let mut builder = Command::builder();
+    builder.executable("cargo".to_owned()).args(vec!["build".to_owned()]);
+    let command = builder.try_build().ok().unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build"]);
+    assert_eq!(command.current_dir, None);
+}
@@ -0,0 +1,47 @@
+// #[builder(default = ...)] gives the value of the field which is used when
+// its setter is not called, so that the field is not required. The default is
+// able to be a path to a const or a static such as `DEFAULT_TIMEOUT`, or an
+// expression written in a string literal.
+
+use derive_builder::Builder;
+
+const DEFAULT_TIMEOUT: u64 = 30;
+static DEFAULT_SHELL: &str = "/bin/sh";
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(default = DEFAULT_TIMEOUT)]
+    timeout: u64,
+    #[builder(default = "DEFAULT_SHELL.to_owned()")]
+    shell: String,
+    #[builder(default = 3)]
+    retries: u32,
+    #[builder(default = "Some(\"/\".to_owned())")]
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.timeout, 30);
+    assert_eq!(command.shell, "/bin/sh");
+    assert_eq!(command.retries, 3);
+    assert_eq!(command.current_dir, Some("/".to_owned()));
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .timeout(60)
+        .shell("/bin/bash".to_owned())
+        .retries(0)
+        .current_dir("..".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.timeout, 60);
+    assert_eq!(command.shell, "/bin/bash");
+    assert_eq!(command.retries, 0);
+    assert_eq!(command.current_dir, Some("..".to_owned()));
+}
@@ -0,0 +1,30 @@
+// The builder has `is_complete`, which tells whether every required field is
+// set, that is, whether `build` does not fail by a missing field.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    args: Vec<String>,
+    env: Vec<String>,
+    current_dir: Option<String>,
+    #[builder(default = 30)]
+    timeout: u64,
+}
+
+fn main() {
+    let mut builder = Command::builder();
+    assert!(!builder.is_complete());
+
+    builder.args(vec![]).current_dir("..".to_owned()).timeout(60);
+    assert!(!builder.is_complete());
+
+    builder.executable("cargo".to_owned());
+    assert!(builder.is_complete());
+
+    let command = builder.build().unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert!(command.env.is_empty());
+    assert_eq!(command.timeout, 60);
+}
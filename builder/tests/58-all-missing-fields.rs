@@ -0,0 +1,39 @@
+// `build` checks every required field before taking any value out of the
+// builder, and reports all the fields which are not set by one
+// CommandBuilderError.
+// The builder is left as it is, so that it is able to be built after the
+// missing fields are set.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    program: String,
+    args: Vec<String>,
+    timeout: u64,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let mut builder = Command::builder();
+    builder
+        .program("build".to_owned())
+        .args(vec!["--release".to_owned()])
+        .current_dir("..".to_owned());
+
+    let err = builder.build().err().unwrap();
+    assert_eq!(err.fields(), ["executable", "timeout"]);
+    assert_eq!(
+        err.to_string(),
+        "fields `executable`, `timeout` were not set"
+    );
+
+    builder.executable("cargo".to_owned()).timeout(60);
+    let command = builder.build().unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.program, "build");
+    assert_eq!(command.args, vec!["--release"]);
+    assert_eq!(command.timeout, 60);
+    assert_eq!(command.current_dir, Some("..".to_owned()));
+}
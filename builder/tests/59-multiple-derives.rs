@@ -0,0 +1,32 @@
+// The error type is named after the builder, such as `CommandBuilderError`,
+// so that the structs in the same module are able to derive Builder side by
+// side.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    args: Vec<String>,
+}
+
+#[derive(Builder)]
+pub struct Shell {
+    program: String,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert!(command.args.is_empty());
+
+    let result: Result<Shell, ShellBuilderError> = Shell::builder().build();
+    assert_eq!(result.err().unwrap().fields(), ["program"]);
+
+    let result: Result<Command, CommandBuilderError> = Command::builder().build();
+    assert_eq!(result.err().unwrap().fields(), ["executable"]);
+}
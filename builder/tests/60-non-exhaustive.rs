@@ -0,0 +1,44 @@
+// A #[non_exhaustive] struct is able to derive Builder, since the builder is
+// defined in the same crate as the struct, where the struct literal is
+// allowed. The builder is the way for the other crates to construct it, with
+// or without #[builder(build_with = "...")].
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[non_exhaustive]
+pub struct Command {
+    pub executable: String,
+    pub current_dir: Option<String>,
+}
+
+#[derive(Builder)]
+#[builder(build_with = "Shell::new")]
+#[non_exhaustive]
+pub struct Shell {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+impl Shell {
+    fn new(program: String, args: Vec<String>) -> Self {
+        Shell { program, args }
+    }
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.current_dir, None);
+
+    let shell = Shell::builder()
+        .program("bash".to_owned())
+        .args(vec!["-c".to_owned()])
+        .build()
+        .unwrap();
+    assert_eq!(shell.program, "bash");
+    assert_eq!(shell.args, vec!["-c"]);
+}
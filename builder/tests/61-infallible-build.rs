@@ -0,0 +1,39 @@
+// If no field is able to be missing, that is, every field is Option, Vec or
+// has #[builder(default = ...)], `build` returns the struct itself instead of
+// Result. #[builder(fallible)] keeps Result, so that a required field is able
+// to be added later without breaking the callers.
+
+mod options {
+    use derive_builder::Builder;
+
+    #[derive(Builder)]
+    pub struct Options {
+        pub verbose: Option<bool>,
+        pub args: Vec<String>,
+        #[builder(default = 4)]
+        pub jobs: u32,
+    }
+}
+
+mod fallible {
+    use derive_builder::Builder;
+
+    #[derive(Builder)]
+    #[builder(fallible)]
+    pub struct Options {
+        pub verbose: Option<bool>,
+    }
+}
+
+fn main() {
+    let options: options::Options = options::Options::builder()
+        .args(vec!["--release".to_owned()])
+        .build();
+    assert_eq!(options.verbose, None);
+    assert_eq!(options.args, vec!["--release"]);
+    assert_eq!(options.jobs, 4);
+
+    let options: Result<fallible::Options, fallible::OptionsBuilderError> =
+        fallible::Options::builder().verbose(true).build();
+    assert_eq!(options.ok().unwrap().verbose, Some(true));
+}
@@ -0,0 +1,33 @@
+// #[builder(keep_option)] on an Option<T> field makes its setter take the
+// whole Option<T>, so that `None` is able to be set explicitly, and `build`
+// copies it as it is. The field is `None` if the setter is not called.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(keep_option)]
+    current_dir: Option<String>,
+    #[builder(keep_option, default = "Some(30)")]
+    timeout: Option<u64>,
+}
+
+fn main() {
+    let mut builder = Command::builder();
+    builder.executable("cargo".to_owned());
+    let set_current_dir: fn(&mut CommandBuilder, Option<String>) -> &mut CommandBuilder =
+        CommandBuilder::current_dir;
+    set_current_dir(&mut builder, Some("..".to_owned()));
+    let command = builder.build().unwrap();
+    assert_eq!(command.current_dir, Some("..".to_owned()));
+    assert_eq!(command.timeout, Some(30));
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .timeout(None)
+        .build()
+        .unwrap();
+    assert_eq!(command.current_dir, None);
+    assert_eq!(command.timeout, None);
+}
@@ -0,0 +1,44 @@
+// The generated error is an enum, so that the caller is able to tell a
+// required field which is not set from a value rejected by
+// #[builder(validate = "...")].
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(validate = "check_jobs")]
+    jobs: u32,
+}
+
+fn check_jobs(jobs: &u32) -> Result<(), String> {
+    if *jobs == 0 {
+        Err("jobs must be positive".to_owned())
+    } else {
+        Ok(())
+    }
+}
+
+fn main() {
+    match Command::builder().jobs(1).build() {
+        Err(CommandBuilderError::UninitializedFields { names }) => {
+            assert_eq!(names, ["executable"]);
+        }
+        _ => panic!("expected UninitializedFields"),
+    }
+
+    let err = Command::builder()
+        .executable("cargo".to_owned())
+        .jobs(0)
+        .build()
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "field `jobs` is invalid: jobs must be positive");
+    match err {
+        CommandBuilderError::ValidationError { field, message } => {
+            assert_eq!(field, Some("jobs"));
+            assert_eq!(message, "jobs must be positive");
+        }
+        _ => panic!("expected ValidationError"),
+    }
+}
@@ -0,0 +1,26 @@
+// A known key of #[builder(...)] written in the wrong shape, such as
+// `each(arg)` instead of `each = "arg"`, is reported at the key instead of
+// being silently ignored.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(name)]
+#[builder(typestate = "yes")]
+pub struct Shell {
+    program: String,
+}
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(each(arg))]
+    args: Vec<String>,
+    #[builder(default)]
+    jobs: u32,
+    #[builder(into = "no")]
+    executable: String,
+    #[builder(keep_option = true, setter(name))]
+    current_dir: Option<String>,
+}
+
+fn main() {}
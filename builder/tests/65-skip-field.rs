@@ -0,0 +1,32 @@
+// #[builder(skip)] leaves the field out of the builder, and `build` fills it
+// with `default = ...` or `Default::default()`. #[builder(setter(skip))] keeps
+// the field in the builder without its setter, so that it is combined with
+// `default = ...`, or with `each = "..."` to be filled only item by item.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(skip)]
+    history: Vec<String>,
+    #[builder(skip, default = "\"1.0\".to_owned()")]
+    version: String,
+    #[builder(setter(skip), default = 4)]
+    jobs: u32,
+    #[builder(setter(skip), each = "arg")]
+    args: Vec<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert!(command.history.is_empty());
+    assert_eq!(command.version, "1.0");
+    assert_eq!(command.jobs, 4);
+    assert_eq!(command.args, vec!["build"]);
+}
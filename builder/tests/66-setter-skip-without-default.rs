@@ -0,0 +1,15 @@
+// A required field with #[builder(setter(skip))] is never set, so that it
+// needs `default = ...`. #[builder(skip)] is not able to be combined with the
+// attributes of the setter.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(setter(skip))]
+    executable: String,
+    #[builder(skip, rename = "dir")]
+    current_dir: Option<String>,
+}
+
+fn main() {}
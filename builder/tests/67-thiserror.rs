@@ -0,0 +1,35 @@
+// With #[builder(error_derive = "thiserror")] the generated error derives
+// thiserror::Error, so that it composes with the error types of the crate
+// which already uses thiserror, for instance through #[from].
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(error_derive = "thiserror")]
+pub struct Command {
+    executable: String,
+    timeout: u32,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AppError {
+    #[error("failed to build the command")]
+    Build(#[from] CommandBuilderError),
+}
+
+fn command(executable: &str) -> Result<Command, AppError> {
+    let command = Command::builder()
+        .executable(executable.to_owned())
+        .build()?;
+    Ok(command)
+}
+
+fn main() {
+    let err = command("cargo").err().unwrap();
+    let AppError::Build(source) = &err;
+    assert_eq!(source.fields(), ["timeout"]);
+    assert_eq!(source.to_string(), "field `timeout` was not set");
+
+    let source = std::error::Error::source(&err).unwrap();
+    assert_eq!(source.to_string(), "field `timeout` was not set");
+}
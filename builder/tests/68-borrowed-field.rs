@@ -0,0 +1,36 @@
+// A field holding a reference such as `&'a [String]` is a plain required
+// field. The builder holds `Option<&'a [String]>`, the setter takes the
+// reference itself, and `build` moves it into the struct, so that the
+// built struct borrows the data instead of copying it.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command<'a> {
+    executable: &'a str,
+    args: &'a [String],
+    current_dir: Option<&'a str>,
+    #[builder(each = "env")]
+    env: Vec<&'a str>,
+}
+
+fn main() {
+    let executable = String::from("cargo");
+    let args = vec!["build".to_owned(), "--release".to_owned()];
+
+    let command = Command::builder()
+        .executable(&executable)
+        .args(&args)
+        .current_dir("..")
+        .env("RUST_LOG=info")
+        .build()
+        .unwrap();
+
+    assert_eq!(command.executable, "cargo");
+    assert!(std::ptr::eq(command.args, args.as_slice()));
+    assert_eq!(command.current_dir, Some(".."));
+    assert_eq!(command.env, vec!["RUST_LOG=info"]);
+
+    let err = Command::builder().args(&args).build().err().unwrap();
+    assert_eq!(err.fields(), ["executable"]);
+}
@@ -0,0 +1,104 @@
+// The generated code refers to the standard library by the absolute
+// paths such as `::std::option::Option`, so that it neither picks up
+// the items of the user's module which share the names with the
+// prelude, nor breaks in a module without the prelude.
+
+mod shadowed {
+    use derive_builder::Builder;
+
+    #[allow(dead_code)]
+    pub struct Option;
+    #[allow(dead_code)]
+    pub struct Some;
+    #[allow(dead_code)]
+    pub struct None;
+    #[allow(dead_code)]
+    pub struct Ok;
+    #[allow(dead_code)]
+    pub struct Err;
+    #[allow(dead_code)]
+    pub struct Default;
+    pub type Result<T> = std::result::Result<T, std::fmt::Error>;
+
+    #[derive(Builder)]
+    pub struct Command {
+        pub executable: String,
+        #[builder(each = "arg")]
+        pub args: Vec<String>,
+        #[builder(default = "1")]
+        pub jobs: u32,
+        #[builder(validate = "check_timeout")]
+        pub timeout: u32,
+    }
+
+    fn check_timeout(timeout: &u32) -> std::result::Result<(), String> {
+        if *timeout == 0 {
+            std::result::Result::Err("zero".to_owned())
+        } else {
+            std::result::Result::Ok(())
+        }
+    }
+
+    pub fn command() -> Result<Command> {
+        Command::builder()
+            .executable("cargo".to_owned())
+            .timeout(10)
+            .build()
+            .map_err(|_| std::fmt::Error)
+    }
+}
+
+#[no_implicit_prelude]
+mod without_prelude {
+    use ::derive_builder::Builder;
+    use ::std::boxed::Box;
+    use ::std::option::Option;
+    use ::std::string::String;
+    use ::std::vec::Vec;
+
+    #[derive(Builder)]
+    pub struct Command {
+        #[builder(into)]
+        pub executable: String,
+        #[builder(each = "arg")]
+        pub args: Vec<String>,
+        pub current_dir: Option<String>,
+        #[builder(default = "1")]
+        pub jobs: u32,
+        #[builder(validate = "check_timeout")]
+        pub timeout: u32,
+        #[builder(boxed)]
+        pub hook: Box<dyn ::std::ops::Fn() -> u32>,
+    }
+
+    fn check_timeout(timeout: &u32) -> ::std::result::Result<(), String> {
+        if *timeout == 0 {
+            ::std::result::Result::Err(::std::string::ToString::to_string("zero"))
+        } else {
+            ::std::result::Result::Ok(())
+        }
+    }
+}
+
+fn main() {
+    let command = shadowed::command().unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert!(command.args.is_empty());
+    assert_eq!(command.jobs, 1);
+
+    let command = without_prelude::Command::builder()
+        .executable("cargo")
+        .arg("build".to_owned())
+        .timeout(10)
+        .hook(|| 3)
+        .build()
+        .unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build"]);
+    assert_eq!(command.current_dir, None);
+    assert_eq!(command.jobs, 1);
+    assert_eq!((command.hook)(), 3);
+
+    let err = without_prelude::Command::builder().try_build().err().unwrap();
+    assert_eq!(err.len(), 3);
+}
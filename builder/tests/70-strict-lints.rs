@@ -0,0 +1,62 @@
+// The generated items are marked #[automatically_derived] and are
+// documented, so that the derive is able to be used in a crate which
+// denies the common strict lints.
+
+#![deny(missing_docs, missing_debug_implementations, unused_qualifications)]
+#![deny(unreachable_pub, rust_2018_idioms, trivial_casts, unused_results)]
+#![deny(single_use_lifetimes, unused_lifetimes, variant_size_differences)]
+
+//! The crate denying the strict lints.
+
+use derive_builder::Builder;
+
+/// The command to run.
+#[derive(Debug, Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[builder(validate = "check_jobs")]
+    jobs: u32,
+    current_dir: Option<String>,
+}
+
+fn check_jobs(jobs: &u32) -> Result<(), String> {
+    if *jobs == 0 {
+        Err("jobs must be positive".to_owned())
+    } else {
+        Ok(())
+    }
+}
+
+/// The command borrowing its arguments.
+#[derive(Debug, Builder)]
+#[builder(typestate)]
+pub struct Borrowed<'a> {
+    executable: &'a str,
+    args: Option<&'a [String]>,
+}
+
+/// The options which are all able to be omitted.
+#[derive(Debug, Builder)]
+#[builder(pattern = "owned")]
+pub struct Options {
+    verbose: Option<bool>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .jobs(1)
+        .build()
+        .unwrap();
+    assert_eq!(command.args, ["build"]);
+    assert_eq!(command.current_dir, None);
+
+    let borrowed = Borrowed::builder().executable("cargo").build();
+    assert_eq!(borrowed.executable, "cargo");
+    assert_eq!(borrowed.args, None);
+
+    assert_eq!(Options::builder().build().verbose, None);
+}
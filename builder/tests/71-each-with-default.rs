@@ -0,0 +1,14 @@
+// #[builder(default = ...)] is not able to be used with #[builder(each = "...")],
+// because the items pushed by `each` start from an empty collection, so that the
+// default would be silently discarded as soon as an item is pushed.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg", default = "Some(Vec::new())")]
+    args: Option<Vec<String>>,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+// #[builder(setter(skip))] removes the setter of the field, so that the
+// attributes which only change the setter, such as `rename` and `into`,
+// would have no effect and are reported instead.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(setter(skip), default = 30, rename = "timeout_secs")]
+    timeout: u64,
+}
+
+fn main() {}
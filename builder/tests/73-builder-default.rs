@@ -0,0 +1,35 @@
+// The builder implements `Default`, which is the same as `new`, so that it
+// is able to be used by the generic code which takes `T: Default`.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[builder(default = 30)]
+    timeout: u64,
+}
+
+#[derive(Builder)]
+#[builder(typestate)]
+pub struct Wrapper<T> {
+    value: T,
+}
+
+fn fresh<B: Default>() -> B {
+    B::default()
+}
+
+fn main() {
+    let mut builder: CommandBuilder = fresh();
+    assert!(!builder.is_complete());
+    let command = builder.executable("cargo".to_owned()).build().unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert!(command.args.is_empty());
+    assert_eq!(command.timeout, 30);
+
+    let builder: WrapperBuilder<u8, false> = Default::default();
+    assert_eq!(builder.value(1).build().value, 1);
+}
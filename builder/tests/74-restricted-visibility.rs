@@ -0,0 +1,62 @@
+// The builder, its methods and the generated error have the visibility of
+// the struct, such as `pub(crate)` or `pub(super)`, so that they are
+// nameable wherever the struct is, and no more public than the struct.
+
+#![deny(unreachable_pub, private_interfaces)]
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub(crate) struct Command {
+    executable: String,
+    current_dir: Option<String>,
+}
+
+mod outer {
+    pub(crate) mod inner {
+        use derive_builder::Builder;
+
+        #[derive(Builder)]
+        pub(super) struct Job {
+            pub(super) name: String,
+        }
+
+        #[derive(Builder)]
+        #[builder(module = "task_builder")]
+        pub(in crate::outer) struct Task {
+            pub(in crate::outer) name: String,
+        }
+    }
+
+    pub(crate) fn missing_fields() -> Vec<&'static str> {
+        let job: Result<inner::Job, inner::JobBuilderError> = inner::Job::builder().build();
+        let task: Result<inner::Task, inner::task_builder::BuildError> =
+            inner::Task::builder().build();
+        let mut fields = job.err().unwrap().fields().to_vec();
+        fields.extend(task.err().unwrap().fields());
+        fields
+    }
+
+    pub(crate) fn names() -> (String, String) {
+        let mut job: inner::JobBuilder = inner::Job::builder();
+        let mut task: inner::TaskBuilder = inner::Task::builder();
+        let job = job.name("build".to_owned()).build().unwrap();
+        let task = task.name("test".to_owned()).build().unwrap();
+        (job.name, task.name)
+    }
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.current_dir, None);
+
+    let err: CommandBuilderError = Command::builder().build().err().unwrap();
+    assert_eq!(err.fields(), ["executable"]);
+
+    assert_eq!(outer::missing_fields(), ["name", "name"]);
+    assert_eq!(outer::names(), ("build".to_owned(), "test".to_owned()));
+}
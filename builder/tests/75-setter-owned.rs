@@ -0,0 +1,32 @@
+// #[builder(setter(owned))] is the same as #[builder(pattern = "owned")]:
+// the setters take the builder by value and return it, and `build` consumes
+// the builder, so that the whole chain is written by value.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(setter(owned, into))]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+}
+
+fn with_args(builder: CommandBuilder) -> CommandBuilder {
+    builder.arg("build".to_owned()).arg("--release".to_owned())
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo")
+        .current_dir("..")
+        .build()
+        .unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.current_dir.as_deref(), Some(".."));
+
+    let builder = CommandBuilder::new().executable("cargo");
+    let command = with_args(builder).build().unwrap();
+    assert_eq!(command.args, vec!["build", "--release"]);
+}
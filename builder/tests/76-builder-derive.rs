@@ -0,0 +1,39 @@
+// #[builder(derive(...))] puts the derives on the builder struct, so that a
+// partially filled builder is able to be cloned, compared and printed.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(derive(Clone, Debug, PartialEq))]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+}
+
+#[derive(Builder)]
+#[builder(typestate, derive(Clone, Debug))]
+pub struct Wrapper<T> {
+    value: T,
+}
+
+fn main() {
+    let mut base = Command::builder();
+    base.executable("cargo".to_owned());
+
+    let mut build = base.clone();
+    build.arg("build".to_owned());
+    let mut test = base.clone();
+    test.arg("test".to_owned());
+    assert_ne!(build, test);
+    assert_eq!(base, *CommandBuilder::new().executable("cargo".to_owned()));
+
+    assert_eq!(build.build().unwrap().args, vec!["build"]);
+    assert_eq!(test.build().unwrap().args, vec!["test"]);
+    assert!(format!("{:?}", base).contains("cargo"));
+
+    let wrapper = Wrapper::builder().value(1).clone();
+    assert!(format!("{:?}", wrapper).contains("value"));
+    assert_eq!(wrapper.build().value, 1);
+}
@@ -0,0 +1,16 @@
+// The fields of the builder are not checked to implement the derives given by
+// #[builder(derive(...))], so that the error of the derive points at the field
+// of the original struct which does not implement it.
+
+use derive_builder::Builder;
+
+pub struct Handle;
+
+#[derive(Builder)]
+#[builder(derive(Clone))]
+pub struct Command {
+    executable: String,
+    handle: Handle,
+}
+
+fn main() {}
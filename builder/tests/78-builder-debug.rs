@@ -0,0 +1,49 @@
+// #[builder(debug)] implements `Debug` for the builder, which prints whether
+// each field is set, and prints `<redacted>` instead of the value of the field
+// with #[builder(sensitive)], so that the state of the builder is able to be
+// logged without leaking a secret.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(debug)]
+pub struct Login {
+    user: String,
+    #[builder(sensitive)]
+    password: String,
+    #[builder(each = "scope")]
+    scopes: Vec<String>,
+    #[builder(each = "token", sensitive)]
+    tokens: Vec<String>,
+    server: Option<String>,
+}
+
+#[derive(Builder)]
+#[builder(debug, typestate)]
+pub struct Wrapper<T> {
+    value: T,
+}
+
+fn main() {
+    let mut builder = Login::builder();
+    assert_eq!(
+        format!("{:?}", builder),
+        "LoginBuilder { user: <unset>, password: <unset>, scopes: [], tokens: <redacted>, server: <unset> }",
+    );
+
+    builder
+        .user("alice".to_owned())
+        .password("hunter2".to_owned())
+        .scope("read".to_owned())
+        .token("secret".to_owned());
+    let debug = format!("{:?}", builder);
+    assert_eq!(
+        debug,
+        "LoginBuilder { user: \"alice\", password: <redacted>, scopes: [\"read\"], tokens: <redacted>, server: <unset> }",
+    );
+    assert!(!debug.contains("hunter2"));
+    assert!(!debug.contains("secret"));
+
+    let wrapper = Wrapper::builder().value(1);
+    assert_eq!(format!("{:?}", wrapper), "WrapperBuilder { value: 1 }");
+}
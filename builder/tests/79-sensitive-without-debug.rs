@@ -0,0 +1,15 @@
+// #[builder(sensitive)] only takes effect on the `Debug` implemented by
+// #[builder(debug)], so that it is an error without it rather than a
+// promise which is silently broken by `derive(Debug)`.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(derive(Debug))]
+pub struct Login {
+    user: String,
+    #[builder(sensitive)]
+    password: String,
+}
+
+fn main() {}
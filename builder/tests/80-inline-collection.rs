@@ -0,0 +1,54 @@
+// A fixed-capacity collection such as `SmallVec<[T; N]>` takes its backing
+// array as the type argument, so that the item of #[builder(each = "...")]
+// is the element of the array rather than the array itself.
+
+use derive_builder::Builder;
+
+pub trait Array {
+    type Item;
+}
+
+impl<T, const N: usize> Array for [T; N] {
+    type Item = T;
+}
+
+// A minimal stand-in for `smallvec::SmallVec`.
+pub struct SmallVec<A: Array> {
+    items: Vec<A::Item>,
+}
+
+impl<A: Array> SmallVec<A> {
+    pub fn new() -> Self {
+        SmallVec { items: Vec::new() }
+    }
+
+    pub fn push(&mut self, item: A::Item) {
+        self.items.push(item);
+    }
+
+    pub fn as_slice(&self) -> &[A::Item] {
+        &self.items
+    }
+}
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg", via = "push")]
+    args: SmallVec<[String; 4]>,
+    #[builder(each = "port", via = "push")]
+    ports: Option<SmallVec<[u16; 2]>>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .args_all(vec!["--release".to_owned()])
+        .port(8080)
+        .build()
+        .unwrap();
+
+    assert_eq!(command.args.as_slice(), ["build", "--release"]);
+    assert_eq!(command.ports.unwrap().as_slice(), [8080]);
+}
@@ -0,0 +1,49 @@
+// #[builder(merge)] generates `merge`, which merges another builder into this
+// one, so that a configuration is able to be composed from several sources.
+// The fields set in the other builder override the ones of this builder, and
+// so do the collections which are not empty in the other builder, unless
+// `merge = "append"` appends their items.
+
+use derive_builder::Builder;
+use std::collections::HashMap;
+
+#[derive(Builder)]
+#[builder(merge)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg", merge = "append")]
+    args: Vec<String>,
+    #[builder(each = "env", map)]
+    env: HashMap<String, String>,
+    #[builder(each = "feature")]
+    features: Option<Vec<String>>,
+    current_dir: Option<String>,
+    #[builder(default = 30)]
+    timeout: u64,
+}
+
+fn main() {
+    let mut defaults = Command::builder();
+    defaults
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .env("RUST_LOG".to_owned(), "info".to_owned())
+        .current_dir("/".to_owned())
+        .timeout(60);
+
+    let mut overrides = Command::builder();
+    overrides
+        .arg("--release".to_owned())
+        .env("RUST_LOG".to_owned(), "debug".to_owned())
+        .feature("serde".to_owned())
+        .current_dir("/tmp".to_owned());
+
+    let command = defaults.merge(overrides).build().unwrap();
+
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build", "--release"]);
+    assert_eq!(command.env["RUST_LOG"], "debug");
+    assert_eq!(command.features, Some(vec!["serde".to_owned()]));
+    assert_eq!(command.current_dir.as_deref(), Some("/tmp"));
+    assert_eq!(command.timeout, 60);
+}
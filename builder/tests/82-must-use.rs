@@ -0,0 +1,29 @@
+// `builder`, the setters of the owned pattern and `build` returning the struct
+// are #[must_use], so that a builder which is dropped without calling `build`
+// is reported. The setters taking `&mut self` are not, because the change is
+// already written into the builder, so that they are called as statements.
+
+#![deny(unused_must_use)]
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(pattern = "owned")]
+pub struct Command {
+    executable: String,
+    current_dir: Option<String>,
+}
+
+#[derive(Builder)]
+pub struct Task {
+    name: String,
+}
+
+fn main() {
+    let mut task = Task::builder();
+    task.name("lint".to_owned());
+
+    Command::builder().executable("cargo".to_owned());
+
+    Command::builder().executable("cargo".to_owned()).build();
+}
@@ -0,0 +1,27 @@
+// #[builder(no_must_use)] removes #[must_use] from the generated methods, such
+// as `builder`, the setters of the owned pattern and `build`.
+
+#![deny(unused_must_use)]
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(no_must_use, pattern = "owned")]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+}
+
+fn main() {
+    Command::builder();
+    Command::builder().executable("cargo".to_owned());
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .args_all(vec!["--release".to_owned()])
+        .build()
+        .unwrap();
+    assert_eq!(command.args, vec!["build", "--release"]);
+}
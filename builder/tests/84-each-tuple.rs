@@ -0,0 +1,31 @@
+// The item of #[builder(each = "...")] on `Vec<(String, u32)>` is the tuple
+// itself, so that the method takes a single `(String, u32)`.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Histogram {
+    #[builder(each = "pair")]
+    pairs: Vec<(String, u32)>,
+    #[builder(each = "point")]
+    points: Option<Vec<(i32, i32, i32)>>,
+}
+
+fn main() {
+    let histogram = Histogram::builder()
+        .pair(("apple".to_owned(), 3))
+        .pair(("banana".to_owned(), 5))
+        .pairs_all(vec![("cherry".to_owned(), 1)])
+        .point((1, 2, 3))
+        .build();
+
+    assert_eq!(
+        histogram.pairs,
+        vec![
+            ("apple".to_owned(), 3),
+            ("banana".to_owned(), 5),
+            ("cherry".to_owned(), 1),
+        ]
+    );
+    assert_eq!(histogram.points, Some(vec![(1, 2, 3)]));
+}
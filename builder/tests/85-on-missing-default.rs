@@ -0,0 +1,54 @@
+// #[builder(on_missing = "default")] builds every unset required field from
+// its `Default` instead of reporting it, so that `build` never fails and
+// returns the struct itself. A field marked by #[builder(required)] is still
+// required, and `on_missing = "error"` is the same as not giving the policy.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(on_missing = "default")]
+pub struct Command {
+    executable: String,
+    args: Vec<String>,
+    jobs: u32,
+    #[builder(default = "String::from(\"/\")")]
+    current_dir: String,
+}
+
+#[derive(Builder)]
+#[builder(on_missing = "default")]
+pub struct Job {
+    name: String,
+    #[builder(required)]
+    priority: Option<u8>,
+}
+
+#[derive(Builder)]
+#[builder(on_missing = "error")]
+pub struct Task {
+    name: String,
+}
+
+fn main() {
+    let command: Command = Command::builder().build();
+    assert_eq!(command.executable, "");
+    assert!(command.args.is_empty());
+    assert_eq!(command.jobs, 0);
+    assert_eq!(command.current_dir, "/");
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .jobs(4)
+        .build();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.jobs, 4);
+
+    assert!(Job::builder().build().is_err());
+    let job = Job::builder().priority(Some(1)).build().unwrap();
+    assert_eq!(job.name, "");
+    assert_eq!(job.priority, Some(1));
+
+    assert!(Task::builder().build().is_err());
+    let task = Task::builder().name("lint".to_owned()).build().unwrap();
+    assert_eq!(task.name, "lint");
+}
@@ -0,0 +1,39 @@
+// #[builder(clone_build)] makes `build` take `&self` and clone the values of
+// the fields instead of taking them out, so that the same builder is able to
+// build several structs. The fields must implement `Clone` only with it.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(clone_build)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+}
+
+#[derive(Builder)]
+#[builder(clone_build, pattern = "owned")]
+pub struct Job<T> {
+    name: String,
+    payload: T,
+}
+
+fn main() {
+    let mut builder = Command::builder();
+    builder.executable("cargo".to_owned()).arg("build".to_owned());
+
+    let build = builder.build().unwrap();
+    let check = builder.executable("cargo".to_owned()).build().unwrap();
+    assert_eq!(build.executable, "cargo");
+    assert_eq!(build.args, vec!["build"]);
+    assert_eq!(check.args, vec!["build"]);
+    assert_eq!(check.current_dir, None);
+
+    let builder = Job::builder().name("lint".to_owned()).payload(vec![1, 2]);
+    let first = builder.build().unwrap();
+    let second = builder.build().unwrap();
+    assert_eq!(first.payload, second.payload);
+    assert_eq!(second.name, "lint");
+}
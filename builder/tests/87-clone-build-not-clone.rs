@@ -0,0 +1,15 @@
+// #[builder(clone_build)] requires `Clone` on the types of the fields since
+// `build` clones their values, which is reported on the derive.
+
+use derive_builder::Builder;
+
+pub struct Handle;
+
+#[derive(Builder)]
+#[builder(clone_build)]
+pub struct Command {
+    executable: String,
+    handle: Handle,
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+// The attributes other than #[builder(...)] are left to their own derives and
+// macros, even the ones shaped like it such as #[serde(rename = "...")] next
+// to #[builder(each = "...")], so that the struct is able to carry #[repr(C)]
+// and derive Serialize and Deserialize as well.
+
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+#[derive(Builder, Serialize, Deserialize, Debug, PartialEq)]
+#[repr(C)]
+#[serde(rename_all = "camelCase")]
+pub struct Command {
+    /// The program to run.
+    #[serde(rename = "exe")]
+    executable: String,
+    #[serde(rename = "each", default)]
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[builder(default = "Some(1)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[allow(dead_code)]
+    max_jobs: Option<u32>,
+}
+
+fn assert_serde<T: Serialize + for<'de> Deserialize<'de>>(_: &T) {}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .build()
+        .unwrap();
+    assert_serde(&command);
+    assert_eq!(command.args, vec!["build"]);
+    assert_eq!(command.max_jobs, Some(1));
+}
@@ -0,0 +1,49 @@
+// `reset` returns the builder to the state `new` produces, since `build` takes
+// the values out of the builder and leaves it half empty. With the owned
+// pattern and #[builder(typestate)], it takes the builder by value and returns
+// the new one.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[builder(default = "String::from(\"/\")")]
+    current_dir: String,
+    env: Option<String>,
+}
+
+#[derive(Builder)]
+#[builder(typestate)]
+pub struct Job {
+    name: String,
+    priority: Option<u8>,
+}
+
+fn main() {
+    let mut builder = Command::builder();
+    builder
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .current_dir("src".to_owned());
+    let command = builder.build().unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.current_dir, "src");
+
+    builder.env("RUST_LOG=debug".to_owned());
+    assert!(builder.reset().build().is_err());
+
+    builder.reset().executable("rustc".to_owned()).arg("--version".to_owned());
+    let command = builder.build().unwrap();
+    assert_eq!(command.executable, "rustc");
+    assert_eq!(command.args, vec!["--version"]);
+    assert_eq!(command.current_dir, "/");
+    assert_eq!(command.env, None);
+
+    let builder = Job::builder().name("lint".to_owned()).priority(1);
+    let job = builder.reset().name("test".to_owned()).build();
+    assert_eq!(job.name, "test");
+    assert_eq!(job.priority, None);
+}
@@ -0,0 +1,84 @@
+// Several structs are able to share one error type of the crate by giving its
+// path to #[builder(error = ...)], which is also able to be written without
+// quotes, instead of a `{Builder}Error` generated for each of them.
+
+use derive_builder::Builder;
+
+#[derive(Debug, PartialEq)]
+pub enum BuilderError {
+    MissingField(&'static str),
+    InvalidField(&'static str, String),
+}
+
+impl BuilderError {
+    pub fn missing_field(field: &'static str) -> Self {
+        BuilderError::MissingField(field)
+    }
+
+    pub fn invalid_field(field: &'static str, message: String) -> Self {
+        BuilderError::InvalidField(field, message)
+    }
+}
+
+#[derive(Builder)]
+#[builder(error = crate::BuilderError)]
+pub struct Command {
+    executable: String,
+}
+
+#[derive(Builder)]
+#[builder(error = crate::BuilderError)]
+pub struct Job {
+    name: String,
+    #[builder(validate = "check_priority")]
+    priority: u8,
+}
+
+#[derive(Builder)]
+#[builder(error = "BuilderError")]
+pub struct Task {
+    command: Option<String>,
+    job: String,
+}
+
+fn check_priority(priority: &u8) -> Result<(), String> {
+    if *priority > 9 {
+        return Err("must be at most 9".to_owned());
+    }
+    Ok(())
+}
+
+fn build_all() -> Result<(Command, Job, Task), BuilderError> {
+    let command = Command::builder().executable("cargo".to_owned()).build()?;
+    let job = Job::builder().name("lint".to_owned()).priority(1).build()?;
+    let task = Task::builder().job("lint".to_owned()).build()?;
+    Ok((command, job, task))
+}
+
+fn main() {
+    let (command, job, task) = build_all().unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(job.priority, 1);
+    assert_eq!(task.command, None);
+
+    let errors: Vec<BuilderError> = vec![
+        Command::builder().build().err().unwrap(),
+        Job::builder().name("lint".to_owned()).build().err().unwrap(),
+        Job::builder()
+            .name("lint".to_owned())
+            .priority(10)
+            .build()
+            .err()
+            .unwrap(),
+        Task::builder().build().err().unwrap(),
+    ];
+    assert_eq!(
+        errors,
+        vec![
+            BuilderError::MissingField("executable"),
+            BuilderError::MissingField("priority"),
+            BuilderError::InvalidField("priority", "must be at most 9".to_owned()),
+            BuilderError::MissingField("job"),
+        ]
+    );
+}
@@ -0,0 +1,33 @@
+// The builder has `missing_fields`, which returns the names of the required
+// fields which are not set, such as for a list shown to the user before
+// building. They are the same names as `build` reports.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    program: String,
+    args: Vec<String>,
+    current_dir: Option<String>,
+    #[builder(default = 30)]
+    timeout: u64,
+}
+
+fn main() {
+    let mut builder = Command::builder();
+    assert_eq!(builder.missing_fields(), vec!["executable", "program"]);
+    assert_eq!(
+        builder.build().err().unwrap().fields(),
+        builder.missing_fields().as_slice()
+    );
+
+    builder.program("build".to_owned()).timeout(60);
+    assert_eq!(builder.missing_fields(), vec!["executable"]);
+    assert!(!builder.is_complete());
+
+    builder.executable("cargo".to_owned());
+    assert!(builder.missing_fields().is_empty());
+    assert!(builder.is_complete());
+    assert!(builder.build().is_ok());
+}
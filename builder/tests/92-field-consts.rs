@@ -0,0 +1,49 @@
+// The builder has the associated constants `FIELDS` and `REQUIRED_FIELDS`
+// listing the names of the setters, so that they are known without creating
+// a builder. The fields without a setter are not listed, and the ones with a
+// default are not required.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(prefix = "with_")]
+pub struct Command {
+    executable: String,
+    #[builder(rename = "arg_list")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+    #[builder(default = 30)]
+    timeout: u64,
+    #[builder(skip)]
+    pid: u32,
+    #[builder(setter(skip), default = "1")]
+    attempts: u32,
+    #[cfg(any())]
+    disabled: String,
+    r#type: String,
+}
+
+fn main() {
+    assert_eq!(
+        CommandBuilder::FIELDS,
+        &[
+            "with_executable",
+            "with_arg_list",
+            "with_current_dir",
+            "with_timeout",
+            "with_type",
+        ]
+    );
+    assert_eq!(
+        CommandBuilder::REQUIRED_FIELDS,
+        &["with_executable", "with_type"]
+    );
+
+    let command = Command::builder()
+        .with_executable("cargo".to_owned())
+        .with_type("bin".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.pid, 0);
+    assert_eq!(command.attempts, 1);
+}
@@ -0,0 +1,80 @@
+// #[builder(collection = "...")] with the path of a collection other than
+// `Vec`, such as a persistent vector `im::Vector`, makes the field start with
+// `Vector::new()` and adds the items by `Extend`, so that the collection needs
+// no `push`. The type arguments are taken from the field.
+
+use derive_builder::Builder;
+use std::rc::Rc;
+
+mod im {
+    use std::rc::Rc;
+
+    // A persistent vector whose clones share the items.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Vector<T> {
+        items: Rc<Vec<T>>,
+    }
+
+    impl<T: Clone> Vector<T> {
+        pub fn new() -> Self {
+            Vector {
+                items: Rc::new(Vec::new()),
+            }
+        }
+
+        pub fn push_back(&mut self, item: T) {
+            Rc::make_mut(&mut self.items).push(item);
+        }
+
+        pub fn len(&self) -> usize {
+            self.items.len()
+        }
+
+        pub fn shares(&self, other: &Self) -> bool {
+            Rc::ptr_eq(&self.items, &other.items)
+        }
+    }
+
+    impl<T: Clone> Extend<T> for Vector<T> {
+        fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+            for item in items {
+                self.push_back(item);
+            }
+        }
+    }
+}
+
+type Env = im::Vector<(String, String)>;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg", collection = "im::Vector")]
+    args: im::Vector<String>,
+    #[builder(each = "env", collection = "im::Vector<(String, String)>")]
+    env: Env,
+    #[builder(collection = "im::Vector")]
+    features: im::Vector<Rc<str>>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .args_all(vec!["--release".to_owned()])
+        .env(("RUST_LOG".to_owned(), "info".to_owned()))
+        .build()
+        .unwrap();
+    assert_eq!(command.args.len(), 2);
+    assert_eq!(command.env.len(), 1);
+    assert_eq!(command.features, im::Vector::new());
+
+    let features = im::Vector::new();
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .features(features.clone())
+        .build()
+        .unwrap();
+    assert_eq!(command.args.len(), 0);
+    assert!(command.features.shares(&features));
+}
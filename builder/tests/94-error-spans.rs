@@ -0,0 +1,24 @@
+// The errors of the attributes point at the exact token which is wrong, such
+// as the unknown key in the middle of a list or a literal given instead of a
+// key, rather than the whole attribute, and the errors of the types point at
+// the type of the field.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(pattern = "owned", prefx = "with_", merge)]
+pub struct Job {
+    name: String,
+}
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(into, "each", rename = "exe")]
+    executable: String,
+    #[builder(setter(name = "argv", skp), each = "arg")]
+    args: Vec<String>,
+    #[builder(each = "env")]
+    env: String,
+}
+
+fn main() {}
@@ -0,0 +1,62 @@
+// #[builder(try_from)] implements `TryFrom<CommandBuilder>` for the struct by
+// `build`, so that the builder works with the code generic over `TryFrom`.
+// If `build` never fails, `From` is implemented instead, which gives
+// `TryFrom` with `Infallible`.
+
+use derive_builder::Builder;
+use std::convert::{Infallible, TryFrom, TryInto};
+
+#[derive(Builder, Debug)]
+#[builder(try_from)]
+pub struct Command {
+    executable: String,
+    args: Vec<String>,
+}
+
+#[derive(Builder)]
+#[builder(try_from, pattern = "owned")]
+pub struct Job<T> {
+    name: String,
+    payload: T,
+}
+
+#[derive(Builder)]
+#[builder(try_from, clone_build)]
+pub struct Task<T> {
+    payload: Option<T>,
+}
+
+#[derive(Builder)]
+#[builder(try_from, typestate)]
+pub struct Step {
+    name: String,
+}
+
+fn convert<B, T: TryFrom<B>>(builder: B) -> Result<T, T::Error> {
+    T::try_from(builder)
+}
+
+fn main() {
+    let mut builder = Command::builder();
+    builder.executable("cargo".to_owned());
+    let command: Command = convert(builder).unwrap();
+    assert_eq!(command.executable, "cargo");
+
+    let error = Command::try_from(Command::builder()).unwrap_err();
+    assert_eq!(error.fields(), &["executable"]);
+
+    let job: Job<u32> = Job::builder()
+        .name("lint".to_owned())
+        .payload(1)
+        .try_into()
+        .unwrap();
+    assert_eq!(job.payload, 1);
+
+    let mut builder = Task::builder();
+    builder.payload(vec![1]);
+    let task: Result<Task<Vec<i32>>, Infallible> = convert(builder);
+    assert_eq!(task.unwrap().payload, Some(vec![1]));
+
+    let step = Step::from(Step::builder().name("build".to_owned()));
+    assert_eq!(step.name, "build");
+}
@@ -0,0 +1,53 @@
+// #[builder(default_try = ...)] gives the default as `Result`, such as reading
+// an environment variable, which is evaluated only if the setter is not
+// called. Its `Err` fails `build` with `CommandBuilderError::InitError`
+// carrying the boxed error as the source.
+
+use derive_builder::Builder;
+use std::env::VarError;
+use std::error::Error;
+
+#[derive(Builder, Debug)]
+pub struct Command {
+    executable: String,
+    #[builder(default_try = std::env::var("DERIVE_BUILDER_TEST_UNSET_HOME"))]
+    home: String,
+    #[builder(default_try = "parse_jobs(\"4\")")]
+    jobs: u32,
+}
+
+fn parse_jobs(s: &str) -> Result<u32, std::num::ParseIntError> {
+    s.parse()
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .home("/home/user".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.home, "/home/user");
+    assert_eq!(command.jobs, 4);
+
+    let err = Command::builder()
+        .executable("cargo".to_owned())
+        .build()
+        .unwrap_err();
+    assert_eq!(err.fields(), &["home"]);
+    assert_eq!(
+        err.to_string(),
+        "field `home` failed to be initialized: environment variable not found"
+    );
+    match err {
+        CommandBuilderError::InitError { field, ref source } => {
+            assert_eq!(field, "home");
+            assert_eq!(source.downcast_ref::<VarError>(), Some(&VarError::NotPresent));
+        }
+        _ => panic!("expected InitError"),
+    }
+    assert!(err.source().unwrap().is::<VarError>());
+
+    let errors = Command::builder().try_build().unwrap_err();
+    let fields: Vec<_> = errors.iter().map(|err| err.fields()).collect();
+    assert_eq!(fields, [["executable"], ["home"]]);
+}
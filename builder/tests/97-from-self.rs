@@ -0,0 +1,72 @@
+// #[builder(from_self)] adds `to_builder`, which clones the struct into a
+// builder whose fields are all set, next to `From` moving the values, so that
+// an existing value is able to be changed and built again. `to_builder`
+// requires `Clone` on the fields, which is why it is opt-in, while a type
+// parameter is bounded by `Clone` only for `to_builder`.
+
+use derive_builder::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(from_self)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+    #[builder(keep_option)]
+    log_level: Option<u8>,
+    #[builder(default = 30)]
+    timeout: u64,
+    #[builder(skip)]
+    pid: u32,
+}
+
+#[derive(Clone)]
+pub struct Handle(u32);
+
+// not `Clone`, which is only moved by `From`.
+pub struct Payload(Vec<i32>);
+
+#[derive(Builder)]
+#[builder(from_self, typestate, pattern = "owned")]
+pub struct Job<T> {
+    name: String,
+    payload: T,
+    handle: Option<Handle>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .log_level(Some(2))
+        .timeout(60)
+        .build()
+        .unwrap();
+
+    let release = command
+        .to_builder()
+        .arg("--release".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(release.args, vec!["build", "--release"]);
+    assert_eq!(release.log_level, Some(2));
+    assert_eq!(release.timeout, 60);
+
+    let mut builder = CommandBuilder::from(command);
+    builder.current_dir("..".to_owned());
+    let command = builder.build().unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.current_dir.as_deref(), Some(".."));
+    assert_eq!(command.pid, 0);
+
+    let job = Job::builder()
+        .name("lint".to_owned())
+        .payload(Payload(vec![1]))
+        .handle(Handle(7))
+        .build();
+    let job = JobBuilder::from(job).name("test".to_owned()).build();
+    assert_eq!(job.name, "test");
+    assert_eq!(job.payload.0, vec![1]);
+    assert_eq!(job.handle.unwrap().0, 7);
+}
@@ -0,0 +1,38 @@
+// #[builder(each = "...", into)] makes the setter of one item take
+// `impl Into<T>` as the other setters with `into` do, so that string slices
+// are able to be pushed into a `Vec<String>`. The method setting all items at
+// once converts each of them in the same way.
+
+use derive_builder::Builder;
+use std::path::PathBuf;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg", into)]
+    args: Vec<String>,
+    #[builder(each = "path", into)]
+    paths: Vec<PathBuf>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build")
+        .arg(String::from("--release"))
+        .path("src")
+        .paths_all(vec!["tests", "examples"])
+        .build()
+        .unwrap();
+
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build", "--release"]);
+    assert_eq!(
+        command.paths,
+        vec![
+            PathBuf::from("src"),
+            PathBuf::from("tests"),
+            PathBuf::from("examples"),
+        ]
+    );
+}
@@ -0,0 +1,59 @@
+// #[builder(merge = "append")] or #[builder(merge = "replace")] on a
+// collection chooses how `merge` combines it with the one of the other
+// builder. A collection is replaced by default, but only if it is not empty
+// in the other builder, so that a layer which leaves it unset keeps the items
+// of the lower layers.
+
+use derive_builder::Builder;
+use std::collections::BTreeMap;
+
+#[derive(Builder, Debug)]
+#[builder(merge)]
+pub struct Config {
+    executable: String,
+    #[builder(each = "arg", merge = "append")]
+    args: Vec<String>,
+    #[builder(each = "feature", merge = "replace")]
+    features: Vec<String>,
+    #[builder(each = "include")]
+    includes: Vec<String>,
+    #[builder(each = "env", map, merge = "append")]
+    env: BTreeMap<String, String>,
+    #[builder(each = "target")]
+    targets: Option<Vec<String>>,
+    jobs: Option<u32>,
+}
+
+fn main() {
+    let mut defaults = Config::builder();
+    defaults
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .feature("default".to_owned())
+        .include("src".to_owned())
+        .env("RUST_LOG".to_owned(), "info".to_owned())
+        .target("x86_64".to_owned())
+        .jobs(4);
+
+    let mut file = Config::builder();
+    file.arg("--release".to_owned())
+        .feature("serde".to_owned())
+        .env("CC".to_owned(), "clang".to_owned());
+
+    let mut flags = Config::builder();
+    flags
+        .arg("--verbose".to_owned())
+        .include("tests".to_owned())
+        .targets(vec![]);
+
+    let config = defaults.merge(file).merge(flags).build().unwrap();
+
+    assert_eq!(config.executable, "cargo");
+    assert_eq!(config.args, vec!["build", "--release", "--verbose"]);
+    assert_eq!(config.features, vec!["serde"]);
+    assert_eq!(config.includes, vec!["tests"]);
+    assert_eq!(config.env.len(), 2);
+    assert_eq!(config.env["RUST_LOG"], "info");
+    assert_eq!(config.targets, Some(vec![]));
+    assert_eq!(config.jobs, Some(4));
+}
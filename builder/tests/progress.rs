@@ -10,4 +10,119 @@ fn tests() {
     t.pass("tests/07-repeated-field.rs");
     t.compile_fail("tests/08-unrecognized-attribute.rs");
     t.pass("tests/09-redefined-prelude-types.rs");
+    t.pass("tests/10-builder-name.rs");
+    t.compile_fail("tests/11-invalid-builder-name.rs");
+    t.pass("tests/12-each-and-vec-setter.rs");
+    t.pass("tests/13-setter-into.rs");
+    t.pass("tests/14-owned-pattern.rs");
+    t.compile_fail("tests/15-invalid-pattern.rs");
+    t.pass("tests/16-build-fn.rs");
+    t.pass("tests/17-cow-field.rs");
+    t.pass("tests/18-builder-module.rs");
+    t.pass("tests/19-custom-error.rs");
+    t.compile_fail("tests/20-custom-error-without-missing-field.rs");
+    t.pass("tests/21-builder-visibility.rs");
+    t.pass("tests/22-boxed-field.rs");
+    t.compile_fail("tests/23-boxed-non-box.rs");
+    t.pass("tests/24-cross-module.rs");
+    t.pass("tests/25-build-or-panic.rs");
+    t.pass("tests/26-each-optional-vec.rs");
+    t.pass("tests/27-setter-prefix.rs");
+    t.compile_fail("tests/28-invalid-setter-prefix.rs");
+    t.pass("tests/29-builder-module-path.rs");
+    t.pass("tests/30-entry-name.rs");
+    t.compile_fail("tests/31-method-name-collision.rs");
+    t.pass("tests/32-typestate.rs");
+    t.compile_fail("tests/33-typestate-missing-field.rs");
+    t.pass("tests/34-build-with.rs");
+    t.compile_fail("tests/35-not-struct.rs");
+    t.compile_fail("tests/36-tuple-struct.rs");
+    t.pass("tests/37-generic-field.rs");
+    t.pass("tests/38-required-option.rs");
+    t.compile_fail("tests/39-invalid-each-name.rs");
+    t.pass("tests/40-raw-each-name.rs");
+    t.pass("tests/41-cfg-field.rs");
+    t.compile_fail("tests/42-each-non-vec.rs");
+    t.compile_fail("tests/43-unknown-attribute.rs");
+    t.pass("tests/44-each-all.rs");
+    t.compile_fail("tests/45-duplicate-attribute.rs");
+    t.pass("tests/46-error-trait.rs");
+    t.compile_fail("tests/47-multiple-errors.rs");
+    t.pass("tests/48-local-struct.rs");
+    t.pass("tests/49-setter-name.rs");
+    t.compile_fail("tests/50-invalid-setter-attribute.rs");
+    t.pass("tests/51-each-via.rs");
+    t.pass("tests/52-collection-alias.rs");
+    t.compile_fail("tests/53-attribute-format.rs");
+    t.pass("tests/54-each-map.rs");
+    t.pass("tests/55-try-build.rs");
+    t.pass("tests/56-default-value.rs");
+    t.pass("tests/57-is-complete.rs");
+    t.pass("tests/58-all-missing-fields.rs");
+    t.pass("tests/59-multiple-derives.rs");
+    t.pass("tests/60-non-exhaustive.rs");
+    t.pass("tests/61-infallible-build.rs");
+    t.pass("tests/62-keep-option.rs");
+    t.pass("tests/63-error-kind.rs");
+    t.compile_fail("tests/64-attribute-shape.rs");
+    t.pass("tests/65-skip-field.rs");
+    t.compile_fail("tests/66-setter-skip-without-default.rs");
+    t.pass("tests/67-thiserror.rs");
+    t.pass("tests/68-borrowed-field.rs");
+    t.pass("tests/69-hygiene.rs");
+    t.pass("tests/70-strict-lints.rs");
+    t.compile_fail("tests/71-each-with-default.rs");
+    t.compile_fail("tests/72-setter-skip-with-rename.rs");
+    t.pass("tests/73-builder-default.rs");
+    t.pass("tests/74-restricted-visibility.rs");
+    t.pass("tests/75-setter-owned.rs");
+    t.pass("tests/76-builder-derive.rs");
+    t.compile_fail("tests/77-builder-derive-unsupported-field.rs");
+    t.pass("tests/78-builder-debug.rs");
+    t.compile_fail("tests/79-sensitive-without-debug.rs");
+    t.pass("tests/80-inline-collection.rs");
+    t.pass("tests/81-merge.rs");
+    t.compile_fail("tests/82-must-use.rs");
+    t.pass("tests/83-no-must-use.rs");
+    t.pass("tests/84-each-tuple.rs");
+    t.pass("tests/85-on-missing-default.rs");
+    t.pass("tests/86-clone-build.rs");
+    t.compile_fail("tests/87-clone-build-not-clone.rs");
+    t.pass("tests/88-foreign-attributes.rs");
+    t.pass("tests/89-reset.rs");
+    t.pass("tests/90-shared-error.rs");
+    t.pass("tests/91-missing-fields.rs");
+    t.pass("tests/92-field-consts.rs");
+    t.pass("tests/93-custom-collection.rs");
+    t.compile_fail("tests/94-error-spans.rs");
+    t.pass("tests/95-try-from.rs");
+    t.pass("tests/96-default-try.rs");
+    t.pass("tests/97-from-self.rs");
+    t.pass("tests/98-each-into.rs");
+    t.pass("tests/99-merge-strategy.rs");
+    t.pass("tests/100-extend.rs");
+    t.pass("tests/101-ctor-args.rs");
+    t.compile_fail("tests/102-ctor-args-arity.rs");
+    t.pass("tests/103-sub-builder.rs");
+    t.pass("tests/104-enum.rs");
+    t.pass("tests/105-setter-transform.rs");
+    t.pass("tests/106-flag.rs");
+    t.pass("tests/107-wrap.rs");
+    t.pass("tests/108-with.rs");
+    t.pass("tests/109-default-self.rs");
+    t.pass("tests/110-build-from-template.rs");
+    t.pass("tests/111-build-wrappers.rs");
+    t.pass("tests/112-missing-docs.rs");
+    t.pass("tests/113-set-tracking.rs");
+    t.pass("tests/114-each-btreemap.rs");
+    t.pass("tests/115-once.rs");
+    t.pass("tests/116-keep.rs");
+    t.pass("tests/117-mutators.rs");
+    t.pass("tests/118-raw-field-errors.rs");
+    t.pass("tests/119-method-named-field.rs");
+    t.pass("tests/120-from-round-trip.rs");
+    t.compile_fail("tests/121-sub-builder-ctor-args.rs");
+    t.pass("tests/122-once-from-origin.rs");
+    t.pass("tests/123-retry-build.rs");
+    t.pass("tests/124-retry-try-build.rs");
 }